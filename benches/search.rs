@@ -0,0 +1,130 @@
+//! Compares drawing fresh entropy from `OsRng` on every attempt with incrementing a counter in a single
+//! random base and with carving entropy out of a pre-filled random buffer, both for raw candidate generation
+//! and for complete searches at difficulties 3 and 4. The `parallel` group measures how the multi-threaded search
+//! scales with the number of worker threads.
+//!
+//! Run with `cargo bench --bench search`; the reported throughput is in hashed candidates per second.
+
+use blake2::{Blake2s256, Digest};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pow_account::HashFinder;
+use rand_core::{OsRng, RngCore};
+
+/// Size of the random buffer used by the buffered entropy strategy.
+const BUFFER_BYTES: usize = 4096;
+
+fn blake2s(data: &[u8]) -> [u8; 32] {
+    Blake2s256::digest(data).into()
+}
+
+fn random_entropy_candidate() -> [u8; 32] {
+    let mut entropy = [0u8; 32];
+    OsRng.fill_bytes(&mut entropy);
+    let origin_hash = blake2s(&entropy);
+    blake2s(&origin_hash)
+}
+
+fn buffered_entropy_candidates(buffer: &mut [u8]) -> [u8; 32] {
+    OsRng.fill_bytes(buffer);
+    let mut best = [0xff; 32];
+    for chunk in buffer.chunks_exact(32) {
+        let origin_hash = blake2s(chunk);
+        best = best.min(blake2s(&origin_hash));
+    }
+    best
+}
+
+fn nonce_counter_candidate(base: &[u8; 32], counter: u64) -> [u8; 32] {
+    let mut entropy = *base;
+    for (byte, counter_byte) in entropy[24..].iter_mut().zip(counter.to_be_bytes()) {
+        *byte ^= counter_byte;
+    }
+    let origin_hash = blake2s(&entropy);
+    blake2s(&origin_hash)
+}
+
+fn candidate_generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("candidate");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("random_entropy", |b| {
+        b.iter(|| black_box(random_entropy_candidate()))
+    });
+
+    let mut buffer = vec![0u8; BUFFER_BYTES];
+    group.throughput(Throughput::Elements((BUFFER_BYTES / 32) as u64));
+    group.bench_function("buffered_entropy", |b| {
+        b.iter(|| black_box(buffered_entropy_candidates(&mut buffer)))
+    });
+    group.throughput(Throughput::Elements(1));
+
+    let mut base = [0u8; 32];
+    OsRng.fill_bytes(&mut base);
+    let mut counter = 0u64;
+    group.bench_function("nonce_counter", |b| {
+        b.iter(|| {
+            counter = counter.wrapping_add(1);
+            black_box(nonce_counter_candidate(&base, counter))
+        })
+    });
+
+    group.finish();
+}
+
+fn search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search");
+    group.sample_size(20);
+
+    for leading_zeros in [3u8, 4] {
+        let hash_finder = HashFinder::new(leading_zeros);
+        group.throughput(Throughput::Elements(hash_finder.expected_attempts() as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("random_entropy", leading_zeros),
+            &hash_finder,
+            |b, hash_finder| b.iter(|| black_box(hash_finder.find())),
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("buffered_entropy", leading_zeros),
+            &hash_finder,
+            |b, hash_finder| b.iter(|| black_box(hash_finder.find_buffered(BUFFER_BYTES))),
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("nonce_counter", leading_zeros),
+            &hash_finder,
+            |b, hash_finder| b.iter(|| black_box(hash_finder.find_counter())),
+        );
+
+        #[cfg(feature = "simd")]
+        group.bench_with_input(
+            BenchmarkId::new("lanes", leading_zeros),
+            &hash_finder,
+            |b, hash_finder| b.iter(|| black_box(hash_finder.find_lanes())),
+        );
+    }
+
+    group.finish();
+}
+
+fn parallel_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel");
+    group.sample_size(10);
+
+    let hash_finder = HashFinder::new(5);
+    group.throughput(Throughput::Elements(hash_finder.expected_attempts() as u64));
+
+    for threads in [1usize, 2, 4, 8] {
+        group.bench_with_input(
+            BenchmarkId::new("find_parallel", threads),
+            &threads,
+            |b, &threads| b.iter(|| black_box(hash_finder.find_parallel(threads))),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, candidate_generation, search, parallel_search);
+criterion_main!(benches);
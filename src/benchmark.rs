@@ -0,0 +1,384 @@
+//! Hash rate measurement used to calibrate a difficulty for the local machine.
+
+use std::time::{Duration, Instant};
+
+use blake2::Blake2s256;
+
+use crate::{Entropy, HashFinder};
+
+/// Number of candidates hashed between two reads of the clock.
+const CLOCK_CHECK_INTERVAL: u64 = 64;
+
+/// Number of short samples taken by [`benchmark_hashrate_ci`].
+const CI_SAMPLES: u32 = 10;
+
+/// Two-sided 95% Student's t critical value for `CI_SAMPLES - 1` degrees of freedom.
+const CI_T_CRITICAL: f64 = 2.262;
+
+/// Duration of the hash rate measurement performed by [`HashFinder::is_practical`].
+const PRACTICALITY_BENCHMARK_DURATION: Duration = Duration::from_millis(100);
+
+/// Duration of the hash rate measurement performed by [`HashFinder::calibrate`].
+const CALIBRATION_BENCHMARK_DURATION: Duration = Duration::from_millis(200);
+
+/// Longest expected solve time [`HashFinder::calibrate`] ever selects a difficulty for.
+const MAX_CALIBRATED_SOLVE_TIME: Duration = Duration::from_secs(600);
+
+/// Measures how many candidate hashes per second this machine can produce.
+///
+/// Runs the same loop as [`HashFinder::find`](crate::HashFinder::find) (fresh entropy, origin hash,
+/// target hash) for the given wall-clock duration without testing the result against any target.
+///
+/// # Parameters
+///
+/// - `dur`: How long the measurement should run.
+///
+/// # Returns
+///
+/// The measured number of candidates hashed per second.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use pow_account::benchmark_hashrate;
+///
+/// let hashrate = benchmark_hashrate(Duration::from_millis(20));
+/// assert!(hashrate > 0.0);
+/// ```
+pub fn benchmark_hashrate(dur: Duration) -> f64 {
+    let started_at = Instant::now();
+    let mut attempts: u64 = 0;
+
+    loop {
+        for _ in 0..CLOCK_CHECK_INTERVAL {
+            let origin_hash = Entropy::new().hash::<Blake2s256>();
+            let _ = Entropy::from(origin_hash).hash::<Blake2s256>();
+        }
+        attempts += CLOCK_CHECK_INTERVAL;
+
+        let elapsed = started_at.elapsed();
+        if elapsed >= dur {
+            return attempts as f64 / elapsed.as_secs_f64();
+        }
+    }
+}
+
+/// Measures the hash rate together with a 95% confidence interval.
+///
+/// The duration is split into several equal samples, each measured with [`benchmark_hashrate`].
+/// The point estimate is the mean of the samples and the bounds are `mean ± t * s / sqrt(n)`,
+/// where `s` is the sample standard deviation and `t` is the Student's t critical value.
+/// The lower bound never goes below zero.
+///
+/// # Parameters
+///
+/// - `dur`: Total duration of the measurement across all samples.
+///
+/// # Returns
+///
+/// A tuple of `(estimate, lower, upper)` in hashes per second.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use pow_account::benchmark_hashrate_ci;
+///
+/// let (estimate, lower, upper) = benchmark_hashrate_ci(Duration::from_millis(50));
+/// assert!(lower <= estimate && estimate <= upper);
+/// ```
+pub fn benchmark_hashrate_ci(dur: Duration) -> (f64, f64, f64) {
+    let sample_duration = dur / CI_SAMPLES;
+    let samples: Vec<f64> = (0..CI_SAMPLES)
+        .map(|_| benchmark_hashrate(sample_duration))
+        .collect();
+
+    confidence_interval(&samples)
+}
+
+/// `Calibrator` recommends a difficulty from the hash rate of the machines that will solve it.
+///
+/// A difficulty of `bits` leading zero bits takes `2^bits` attempts on average, so the expected solve time is
+/// `2^bits / hashrate`. The hash rate can be measured on this machine with [`Calibrator::measure_hashrate`], or
+/// measured on the slowest clients the service expects and passed to [`Calibrator::difficulty_for`] directly.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use pow_account::{Calibrator, HashFinder};
+///
+/// let hashrate = Calibrator::measure_hashrate(Duration::from_millis(20));
+/// let bits = Calibrator::difficulty_for(Duration::from_millis(10), hashrate);
+/// let hash_finder = HashFinder::with_bits(bits);
+///
+/// assert_eq!(hash_finder.expected_attempts(), Calibrator::expected_attempts(bits));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Calibrator;
+
+impl Calibrator {
+    /// Measures how many candidate hashes per second this machine can produce
+    ///
+    /// This is the same measurement as [`benchmark_hashrate`].
+    ///
+    /// # Parameters
+    ///
+    /// - `duration`: How long the measurement should run.
+    ///
+    /// # Returns
+    ///
+    /// The measured number of candidates hashed per second.
+    pub fn measure_hashrate(duration: Duration) -> f64 {
+        benchmark_hashrate(duration)
+    }
+
+    /// Returns the number of leading zero bits whose expected solve time at `hashrate` is closest to
+    /// `target_solve_time`
+    ///
+    /// Since every bit doubles the work, the expected solve time lands within a factor of `√2` of the request.
+    /// Requests above 10 minutes are capped, so the difficulty never expects to take longer.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_solve_time`: The expected time an origin hash should take to find.
+    /// - `hashrate`: The number of candidates hashed per second by the solver.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::Calibrator;
+    ///
+    /// assert_eq!(Calibrator::difficulty_for(Duration::from_secs(1), 1_000_000.0), 20);
+    /// ```
+    pub fn difficulty_for(target_solve_time: Duration, hashrate: f64) -> u32 {
+        calibrated_bits(hashrate, target_solve_time)
+    }
+
+    /// Returns the expected number of attempts of a search for `bits` leading zero bits, `2^bits`
+    pub fn expected_attempts(bits: u32) -> f64 {
+        2f64.powi(bits as i32)
+    }
+
+    /// Returns the expected duration of a search for `bits` leading zero bits at `hashrate`
+    ///
+    /// # Returns
+    ///
+    /// This function returns [`Duration::MAX`] if the expected duration doesn't fit in a `Duration` or if
+    /// `hashrate` is not positive.
+    pub fn expected_solve_time(bits: u32, hashrate: f64) -> Duration {
+        Duration::try_from_secs_f64(Calibrator::expected_attempts(bits) / hashrate)
+            .unwrap_or(Duration::MAX)
+    }
+
+    /// Returns the probability that a search for `bits` leading zero bits is still running after `attempts` attempts
+    ///
+    /// Every attempt succeeds independently with a probability of `2^-bits`, so this is `(1 - 2^-bits)^attempts`. It
+    /// bounds how unlucky the slowest clients get: about 5% of the searches take three times the expected attempts.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::Calibrator;
+    ///
+    /// let p = Calibrator::probability_exceeding(20, 3 * (1 << 20));
+    /// assert!((p - 0.0498).abs() < 1e-3);
+    /// ```
+    pub fn probability_exceeding(bits: u32, attempts: u64) -> f64 {
+        if attempts == 0 {
+            return 1.0;
+        }
+        ((-1.0 / Calibrator::expected_attempts(bits)).ln_1p() * attempts as f64).exp()
+    }
+}
+
+impl HashFinder {
+    /// Measures how many candidate hashes per second this machine can produce
+    ///
+    /// This is the same measurement as [`benchmark_hashrate`], exposed next to the constructors so a difficulty can
+    /// be chosen from its result, for instance with [`HashFinder::estimate_duration`].
+    ///
+    /// # Parameters
+    ///
+    /// - `duration`: How long the measurement should run.
+    ///
+    /// # Returns
+    ///
+    /// The measured number of candidates hashed per second.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::HashFinder;
+    ///
+    /// let hashrate = HashFinder::benchmark(Duration::from_millis(20));
+    /// assert!(hashrate > 0.0);
+    /// ```
+    pub fn benchmark(duration: Duration) -> f64 {
+        benchmark_hashrate(duration)
+    }
+
+    /// Returns a HashFinder whose expected solve time on this machine is closest to `target_solve_time`
+    ///
+    /// The local hash rate is measured with [`benchmark_hashrate`] for a short period and the number of leading
+    /// zero bits is chosen so that `2^bits / hashrate` is as close as possible to the requested time, as with
+    /// [`HashFinder::with_bits`]. Since every bit doubles the work, the expected solve time lands within a factor
+    /// of `√2` of the request. Requests above 10 minutes are capped, so the result never expects to take longer.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_solve_time`: The expected time an origin hash should take to find.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::calibrate(Duration::from_millis(10));
+    /// let origin_hash = hash_finder.find();
+    ///
+    /// assert!(hash_finder.check_bytes(&origin_hash));
+    /// ```
+    pub fn calibrate(target_solve_time: Duration) -> Self {
+        let hashrate = Calibrator::measure_hashrate(CALIBRATION_BENCHMARK_DURATION);
+        HashFinder::with_bits(Calibrator::difficulty_for(target_solve_time, hashrate))
+    }
+
+    /// Estimates whether an origin hash can be found on this machine within `max`
+    ///
+    /// The local hash rate is measured with [`benchmark_hashrate`] for a short period and compared with
+    /// [`HashFinder::expected_attempts`]. The result is only an estimate: it depends on the current hardware
+    /// and load, and an individual search can take considerably longer than the expected time.
+    ///
+    /// # Parameters
+    ///
+    /// - `max`: The longest acceptable expected solve time.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `true` if the expected solve time does not exceed `max`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::HashFinder;
+    ///
+    /// assert!(HashFinder::new(1).is_practical(Duration::from_secs(1)));
+    /// ```
+    pub fn is_practical(&self, max: Duration) -> bool {
+        let hashrate = benchmark_hashrate(PRACTICALITY_BENCHMARK_DURATION);
+        self.expected_attempts() / hashrate <= max.as_secs_f64()
+    }
+}
+
+fn calibrated_bits(hashrate: f64, target_solve_time: Duration) -> u32 {
+    let seconds = target_solve_time
+        .min(MAX_CALIBRATED_SOLVE_TIME)
+        .as_secs_f64();
+    let max_bits = (hashrate * MAX_CALIBRATED_SOLVE_TIME.as_secs_f64())
+        .log2()
+        .floor();
+
+    (hashrate * seconds).log2().round().min(max_bits).max(0.0) as u32
+}
+
+fn confidence_interval(samples: &[f64]) -> (f64, f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let margin = CI_T_CRITICAL * variance.sqrt() / n.sqrt();
+
+    (mean, (mean - margin).max(0.0), mean + margin)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn benchmark_returns_a_positive_finite_hashrate() {
+        let hashrate = benchmark_hashrate(Duration::from_millis(20));
+        assert!(hashrate.is_finite() && hashrate > 0.0)
+    }
+
+    #[test]
+    fn associated_benchmark_returns_a_positive_finite_hashrate() {
+        let hashrate = HashFinder::benchmark(Duration::from_millis(50));
+        assert!(hashrate.is_finite() && hashrate > 0.0)
+    }
+
+    #[test]
+    fn confidence_interval_brackets_the_estimate() {
+        let (estimate, lower, upper) = benchmark_hashrate_ci(Duration::from_millis(50));
+        assert!(lower <= estimate && estimate <= upper);
+        assert!(lower >= 0.0 && upper.is_finite())
+    }
+
+    #[test]
+    fn high_difficulty_is_not_practical() {
+        assert!(HashFinder::new(2).is_practical(Duration::from_secs(10)));
+        assert!(!HashFinder::new(30).is_practical(Duration::from_secs(10)))
+    }
+
+    #[test]
+    fn longer_solve_time_calibrates_a_higher_difficulty() {
+        let short = HashFinder::calibrate(Duration::from_millis(1));
+        let long = HashFinder::calibrate(Duration::from_secs(1));
+
+        assert!(short.expected_attempts() < long.expected_attempts())
+    }
+
+    #[test]
+    fn calibrated_bits_round_to_the_nearest_difficulty() {
+        assert_eq!(calibrated_bits(1_000_000.0, Duration::from_secs(1)), 20);
+        assert_eq!(calibrated_bits(1_000_000.0, Duration::from_millis(500)), 19);
+        assert_eq!(calibrated_bits(1_000_000.0, Duration::ZERO), 0);
+        assert_eq!(calibrated_bits(1.0, Duration::from_millis(1)), 0);
+
+        // 2^29 attempts at a million hashes per second take about 9 minutes, 2^30 take about 18
+        assert_eq!(calibrated_bits(1_000_000.0, Duration::from_secs(600)), 29);
+        assert_eq!(calibrated_bits(1_000_000.0, Duration::MAX), 29)
+    }
+
+    #[test]
+    fn calibrator_math_matches_the_hash_finder() {
+        for bits in [0, 1, 12, 40] {
+            assert_eq!(
+                Calibrator::expected_attempts(bits),
+                HashFinder::with_bits(bits).expected_attempts()
+            );
+            assert_eq!(
+                Calibrator::expected_solve_time(bits, 1000.0),
+                HashFinder::with_bits(bits).estimate_duration(1000.0)
+            )
+        }
+        assert_eq!(Calibrator::expected_solve_time(1, 0.0), Duration::MAX);
+        assert_eq!(
+            Calibrator::difficulty_for(Duration::from_millis(500), 1_000_000.0),
+            19
+        )
+    }
+
+    #[test]
+    fn probability_exceeding_decreases_with_attempts() {
+        assert_eq!(Calibrator::probability_exceeding(8, 0), 1.0);
+        assert_eq!(Calibrator::probability_exceeding(0, 0), 1.0);
+        assert_eq!(Calibrator::probability_exceeding(0, 1), 0.0);
+        assert!(
+            Calibrator::probability_exceeding(8, 256) > Calibrator::probability_exceeding(8, 512)
+        );
+        assert!((Calibrator::probability_exceeding(30, 1 << 30) - (-1f64).exp()).abs() < 1e-6)
+    }
+
+    #[test]
+    fn confidence_interval_of_identical_samples_is_a_point() {
+        let samples = [1000.0; CI_SAMPLES as usize];
+        assert_eq!(confidence_interval(&samples), (1000.0, 1000.0, 1000.0))
+    }
+}
@@ -0,0 +1,117 @@
+//! Command-line interface to mint and verify proofs, enabled with the `cli` feature.
+//!
+//! ```text
+//! pow-account find --difficulty 5
+//! pow-account check <ORIGIN_HASH> --difficulty 5
+//! pow-account bench --seconds 3
+//! ```
+//!
+//! Every command prints plain text by default and a JSON object with `--json`. `check` exits with status 0 if the
+//! origin hash meets the difficulty, 1 if it doesn't and 2 if it is malformed, so it can be used in shell conditions.
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use pow_account::{benchmark_hashrate_ci, HashFinder, PowError};
+use serde_json::json;
+
+#[derive(Parser)]
+#[command(
+    name = "pow-account",
+    version,
+    about = "Mint and verify proof-of-work origin hashes"
+)]
+struct Cli {
+    /// Print a JSON object instead of plain text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Find an origin hash and print it in hexadecimal
+    Find {
+        /// Number of leading zeros of the target hash
+        #[arg(short, long, default_value_t = 5)]
+        difficulty: u8,
+    },
+    /// Check that a hexadecimal origin hash meets a difficulty
+    Check {
+        /// The origin hash in hexadecimal
+        origin_hash: String,
+        /// Number of leading zeros of the target hash
+        #[arg(short, long, default_value_t = 5)]
+        difficulty: u8,
+    },
+    /// Measure the number of candidate hashes per second of this machine
+    Bench {
+        /// Duration of the measurement in seconds
+        #[arg(short, long = "seconds", default_value = "3", value_parser = parse_seconds)]
+        duration: Duration,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command, cli.json) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(command: Command, json: bool) -> Result<ExitCode, PowError> {
+    match command {
+        Command::Find { difficulty } => {
+            let solution = HashFinder::try_new(difficulty)?.find_with_stats();
+            match json {
+                true => println!("{}", json!(solution)),
+                false => println!("{}", hex::encode(solution.origin_hash)),
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Check {
+            origin_hash,
+            difficulty,
+        } => {
+            let hash_finder = HashFinder::try_new(difficulty)?;
+            let valid = hash_finder.check(&origin_hash)?;
+            match json {
+                true => println!(
+                    "{}",
+                    json!({ "origin_hash": origin_hash, "difficulty": difficulty, "valid": valid })
+                ),
+                false => println!("{valid}"),
+            }
+            Ok(match valid {
+                true => ExitCode::SUCCESS,
+                false => ExitCode::FAILURE,
+            })
+        }
+        Command::Bench { duration } => {
+            let (estimate, lower, upper) = benchmark_hashrate_ci(duration);
+            match json {
+                true => println!(
+                    "{}",
+                    json!({ "hashrate": estimate, "lower": lower, "upper": upper })
+                ),
+                false => println!("{estimate:.0} hashes/s ({lower:.0} to {upper:.0})"),
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| format!("`{s}` is not a positive number of seconds"))
+}
@@ -0,0 +1,119 @@
+//! Token bucket that is refilled by proof-of-work instead of by time.
+
+use crate::work_of;
+
+/// `WorkBucket` turns origin hashes into rate-limit credit.
+///
+/// Every submitted origin hash adds [`work_of`] to the credit, capped at the bucket capacity, and every allowed
+/// action spends a fixed cost. The bucket does not remember which origin hashes it has seen, so replay protection
+/// has to be handled by the caller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorkBucket {
+    capacity: f64,
+    action_cost: f64,
+    credit: f64,
+}
+
+impl WorkBucket {
+    /// Returns an empty WorkBucket holding at most `capacity` units of work, where each action costs `action_cost`
+    /// # Example
+    /// ```
+    /// use pow_account::WorkBucket;
+    ///
+    /// // Each action requires on average 4096 hashes, and up to 16 actions can be saved up
+    /// let bucket = WorkBucket::new(65536.0, 4096.0);
+    ///
+    /// ```
+    pub fn new(capacity: f64, action_cost: f64) -> Self {
+        WorkBucket {
+            capacity,
+            action_cost,
+            credit: 0.0,
+        }
+    }
+
+    /// Returns the maximum amount of credit the bucket can hold
+    pub fn capacity(&self) -> f64 {
+        self.capacity
+    }
+
+    /// Returns the credit currently available in the bucket
+    pub fn credit(&self) -> f64 {
+        self.credit
+    }
+
+    /// Adds the work of an origin hash to the bucket and spends the cost of one action if enough credit is available.
+    ///
+    /// # Parameters
+    ///
+    /// - `origin`: The submitted origin hash.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `true` if the action is allowed, otherwise it returns `false` and keeps the added credit.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::{HashFinder, WorkBucket};
+    ///
+    /// let mut bucket = WorkBucket::new(65536.0, 4096.0);
+    /// let origin_hash = HashFinder::new(3).find();
+    ///
+    /// assert!(bucket.try_spend(&origin_hash));
+    /// ```
+    pub fn try_spend(&mut self, origin: &[u8; 32]) -> bool {
+        self.credit = (self.credit + work_of(origin)).min(self.capacity);
+
+        match self.credit >= self.action_cost {
+            true => {
+                self.credit -= self.action_cost;
+                true
+            }
+            false => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn origin_hash(origin_hash_hex: &str) -> [u8; 32] {
+        hex::decode(origin_hash_hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn sufficient_work_allows_an_action() {
+        // The target hash starts with 12 zero bits
+        let origin =
+            origin_hash("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
+        let mut bucket = WorkBucket::new(65536.0, 4096.0);
+
+        assert!(bucket.try_spend(&origin));
+        assert_eq!(bucket.credit(), 0.0)
+    }
+
+    #[test]
+    fn insufficient_work_is_accumulated() {
+        // The target hash starts with 1 zero bit
+        let origin =
+            origin_hash("c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa");
+        let mut bucket = WorkBucket::new(65536.0, 4.0);
+
+        assert!(!bucket.try_spend(&origin));
+        assert!(bucket.try_spend(&origin));
+        assert_eq!(bucket.credit(), 0.0)
+    }
+
+    #[test]
+    fn credit_is_capped_at_capacity() {
+        // The target hash starts with 20 zero bits
+        let origin =
+            origin_hash("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e");
+        let mut bucket = WorkBucket::new(8192.0, 4096.0);
+
+        assert!(bucket.try_spend(&origin));
+        assert_eq!(bucket.credit(), bucket.capacity() - 4096.0)
+    }
+}
@@ -0,0 +1,273 @@
+//! Fluent configuration of a [`HashFinder`].
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2s256, Digest};
+
+use crate::{HashFinder, PowError, DEFAULT_ROUNDS, DEFAULT_VERSION, MAX_DIFFICULTY_BITS};
+
+/// `HashFinderBuilder` configures a [`HashFinder`] one setting at a time.
+///
+/// Every setting left untouched keeps the value of [`HashFinder::default`], so `HashFinder::builder().build()` is
+/// equal to `HashFinder::default()`. [`HashFinderBuilder::build`] adjusts out-of-range settings the way the
+/// constructors do, while [`HashFinderBuilder::try_build`] rejects them. The domain is only turned into its tag by
+/// [`HashFinderBuilder::build`], with the digest chosen by then, so the setters can be called in any order.
+///
+/// The builder only covers what a HashFinder stores and what its checks depend on. The number of threads, the timeout
+/// and the random number generator of a search are not part of it: they stay arguments of the search that uses them,
+/// [`HashFinder::find_parallel`], [`HashFinder::find_with_timeout`] and [`HashFinder::find_with_rng`].
+///
+/// # Example
+/// ```
+/// use pow_account::HashFinder;
+/// use sha2::Sha256;
+///
+/// let hash_finder = HashFinder::builder()
+///     .bits(12)
+///     .rounds(3)
+///     .domain(b"service-a")
+///     .digest::<Sha256>()
+///     .build();
+///
+/// let origin_hash = hash_finder.find();
+/// assert!(hash_finder.check_bytes(&origin_hash));
+/// ```
+pub struct HashFinderBuilder<'a, D = Blake2s256> {
+    bits: Option<u32>,
+    version: u8,
+    counter_salt: bool,
+    rounds: u32,
+    domain: Option<&'a [u8]>,
+    digest: PhantomData<fn() -> D>,
+}
+
+impl<D> fmt::Debug for HashFinderBuilder<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashFinderBuilder")
+            .field("bits", &self.bits)
+            .field("version", &self.version)
+            .field("counter_salt", &self.counter_salt)
+            .field("rounds", &self.rounds)
+            .field("domain", &self.domain)
+            .field("digest", &core::any::type_name::<D>())
+            .finish()
+    }
+}
+
+impl<D> Clone for HashFinderBuilder<'_, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for HashFinderBuilder<'_, D> {}
+
+impl Default for HashFinderBuilder<'_> {
+    fn default() -> Self {
+        HashFinderBuilder {
+            bits: None,
+            version: DEFAULT_VERSION,
+            counter_salt: true,
+            rounds: DEFAULT_ROUNDS,
+            domain: None,
+            digest: PhantomData,
+        }
+    }
+}
+
+impl HashFinder {
+    /// Returns a [`HashFinderBuilder`] starting from the settings of [`HashFinder::default`]
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// assert_eq!(HashFinder::builder().bits(16).build(), HashFinder::new(4));
+    ///
+    /// ```
+    pub fn builder<'a>() -> HashFinderBuilder<'a> {
+        HashFinderBuilder::default()
+    }
+}
+
+impl<'a, D> HashFinderBuilder<'a, D> {
+    /// Sets the number of leading zero bits required of target hashes, as with [`HashFinder::with_bits`]
+    pub fn bits(self, bits: u32) -> Self {
+        HashFinderBuilder {
+            bits: Some(bits),
+            ..self
+        }
+    }
+
+    /// Sets the version of the hashing construction, as with [`HashFinder::with_version`]
+    pub fn version(self, version: u8) -> Self {
+        HashFinderBuilder { version, ..self }
+    }
+
+    /// Enables or disables the counter salt, as with [`HashFinder::set_counter_salt`]
+    pub fn counter_salt(self, enabled: bool) -> Self {
+        HashFinderBuilder {
+            counter_salt: enabled,
+            ..self
+        }
+    }
+
+    /// Sets the number of hashing rounds, as with [`HashFinder::with_rounds`]
+    pub fn rounds(self, rounds: u32) -> Self {
+        HashFinderBuilder { rounds, ..self }
+    }
+
+    /// Binds the proofs to an application domain, as with [`HashFinder::with_domain`]
+    pub fn domain(self, domain: &'a [u8]) -> Self {
+        HashFinderBuilder {
+            domain: Some(domain),
+            ..self
+        }
+    }
+
+    /// Hashes with the digest `E` instead, as with [`HashFinder::with_digest`]
+    pub fn digest<E>(self) -> HashFinderBuilder<'a, E> {
+        HashFinderBuilder {
+            bits: self.bits,
+            version: self.version,
+            counter_salt: self.counter_salt,
+            rounds: self.rounds,
+            domain: self.domain,
+            digest: PhantomData,
+        }
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinderBuilder<'_, D> {
+    /// Returns the configured HashFinder
+    pub fn build(self) -> HashFinder<D> {
+        let mut hash_finder = match self.bits {
+            Some(bits) => HashFinder::with_bits(bits),
+            None => HashFinder::default(),
+        }
+        .with_digest::<D>()
+        .with_rounds(self.rounds);
+
+        hash_finder.version = self.version;
+        hash_finder.set_counter_salt(self.counter_salt);
+
+        match self.domain {
+            Some(domain) => hash_finder.with_domain(domain),
+            None => hash_finder,
+        }
+    }
+
+    /// Returns the configured HashFinder, rejecting settings that [`HashFinderBuilder::build`] would adjust
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::TooManyBits`] if the difficulty exceeds [`MAX_DIFFICULTY_BITS`] and
+    /// [`PowError::TooFewRounds`] if the number of rounds is below [`DEFAULT_ROUNDS`].
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::{HashFinder, PowError};
+    ///
+    /// assert_eq!(HashFinder::builder().bits(12).try_build(), Ok(HashFinder::with_bits(12)));
+    /// assert_eq!(
+    ///     HashFinder::builder().bits(300).try_build(),
+    ///     Err(PowError::TooManyBits { bits: 300 })
+    /// );
+    /// assert_eq!(
+    ///     HashFinder::builder().rounds(1).try_build(),
+    ///     Err(PowError::TooFewRounds { rounds: 1 })
+    /// );
+    /// ```
+    pub fn try_build(self) -> Result<HashFinder<D>, PowError> {
+        match self.bits {
+            Some(bits) if bits > MAX_DIFFICULTY_BITS => Err(PowError::TooManyBits { bits }),
+            _ if self.rounds < DEFAULT_ROUNDS => Err(PowError::TooFewRounds {
+                rounds: self.rounds,
+            }),
+            _ => Ok(self.build()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use sha2::Sha256;
+
+    use super::*;
+
+    #[test]
+    fn empty_builder_reproduces_the_default() {
+        assert_eq!(HashFinder::builder().build(), HashFinder::default())
+    }
+
+    #[test]
+    fn builder_matches_the_chained_constructors() {
+        let built = HashFinder::builder()
+            .domain(b"service-a")
+            .version(2)
+            .bits(12)
+            .rounds(3)
+            .counter_salt(false)
+            .build();
+
+        let mut chained = HashFinder::with_version(3, 2)
+            .with_rounds(3)
+            .with_domain(b"service-a");
+        chained.set_counter_salt(false);
+
+        assert_eq!(built, chained);
+        assert!(chained.check_bytes(&built.find()))
+    }
+
+    #[test]
+    fn try_build_rejects_what_build_adjusts() {
+        let builder = HashFinder::builder()
+            .bits(MAX_DIFFICULTY_BITS + 1)
+            .rounds(0);
+        assert_eq!(
+            builder.try_build(),
+            Err(PowError::TooManyBits {
+                bits: MAX_DIFFICULTY_BITS + 1
+            })
+        );
+        assert_eq!(
+            builder.bits(8).try_build(),
+            Err(PowError::TooFewRounds { rounds: 0 })
+        );
+        assert_eq!(
+            builder.bits(8).rounds(DEFAULT_ROUNDS).try_build(),
+            Ok(builder.bits(8).build())
+        );
+        assert_eq!(
+            builder.bits(256).rounds(DEFAULT_ROUNDS).try_build(),
+            Err(PowError::TooManyBits { bits: 256 })
+        );
+        assert_eq!(
+            builder.bits(255).rounds(DEFAULT_ROUNDS).try_build(),
+            Ok(HashFinder::with_bits(255))
+        );
+        assert_eq!(builder.build(), HashFinder::with_bits(MAX_DIFFICULTY_BITS))
+    }
+
+    #[test]
+    fn domain_tag_follows_the_digest_chosen_last() {
+        let built = HashFinder::builder()
+            .domain(b"service-a")
+            .digest::<Sha256>()
+            .build();
+        let chained = HashFinder::default()
+            .with_digest::<Sha256>()
+            .with_domain(b"service-a");
+
+        assert_eq!(built, chained);
+        assert_ne!(
+            built,
+            HashFinder::default()
+                .with_domain(b"service-a")
+                .with_digest::<Sha256>()
+        )
+    }
+}
@@ -0,0 +1,126 @@
+//! Compact summary of a verified origin hash for downstream services.
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::{target_difficulty, HashFinder};
+
+/// `Certificate` summarizes an origin hash that passed [`HashFinder::certify`].
+///
+/// It is much smaller than the origin hash it was created from and can be stored or forwarded instead of it.
+/// A certificate is not signed, so it should only be trusted when it comes from the verifying service itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Certificate {
+    /// The number of leading zeros of the target hash, as reported by [`HashFinder::check_difficulty`].
+    pub difficulty_achieved: u8,
+    /// The identifier of the token, as reported by [`HashFinder::token_id`].
+    pub token_id: [u8; 16],
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Returns a 16-byte identifier of an origin hash
+    ///
+    /// The identifier consists of the last 16 bytes of the target hash. The first bytes are avoided because
+    /// they are mostly the leading zeros required by the difficulty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(3);
+    /// let origin_hash = hash_finder.find();
+    ///
+    /// assert_eq!(hash_finder.token_id(&origin_hash), hash_finder.token_id(&origin_hash));
+    /// ```
+    pub fn token_id(&self, origin_hash: &[u8; 32]) -> [u8; 16] {
+        token_id(&self.target_hash(*origin_hash))
+    }
+
+    /// Verifies an origin hash and summarizes it in a [`Certificate`]
+    ///
+    /// # Parameters
+    ///
+    /// - `origin`: The origin hash to verify.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Some` certificate with the achieved difficulty and the token identifier if the
+    /// origin hash meets the requirement, otherwise it returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(3);
+    /// let certificate = hash_finder.certify(&hash_finder.find()).unwrap();
+    ///
+    /// assert!(certificate.difficulty_achieved >= 3);
+    /// ```
+    pub fn certify(&self, origin: &[u8; 32]) -> Option<Certificate> {
+        let target_hash = self.target_hash(*origin);
+        if !self.meets_target(&target_hash) {
+            return None;
+        }
+
+        // Both fields are read from the same target hash, so the origin is only hashed once
+        Some(Certificate {
+            difficulty_achieved: target_difficulty(&target_hash) as u8,
+            token_id: token_id(&target_hash),
+        })
+    }
+}
+
+/// Returns the identifier of the token whose target hash is `target_hash`, as reported by [`HashFinder::token_id`].
+fn token_id(target_hash: &[u8; 32]) -> [u8; 16] {
+    let mut token_id = [0u8; 16];
+    token_id.copy_from_slice(&target_hash[16..]);
+    token_id
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+
+    fn origin_hash(origin_hash_hex: &str) -> [u8; 32] {
+        hex::decode(origin_hash_hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn certificate_summarizes_a_valid_hash() {
+        // The target hash is 00000c0fd80d3af2a3c41459ebcd9a73adbacd8c442b229e75afad56c2c08f2c
+        let origin =
+            origin_hash("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e");
+
+        let certificate = HashFinder::new(4).certify(&origin).unwrap();
+        assert_eq!(certificate.difficulty_achieved, 5);
+        assert_eq!(
+            hex::encode(certificate.token_id),
+            "adbacd8c442b229e75afad56c2c08f2c"
+        )
+    }
+
+    #[test]
+    fn invalid_hash_is_not_certified() {
+        let origin =
+            origin_hash("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
+        assert_eq!(HashFinder::new(4).certify(&origin), None)
+    }
+
+    #[test]
+    fn certificate_agrees_with_the_separate_queries() {
+        let hash_finder = HashFinder::new(2)
+            .with_rounds(4)
+            .with_domain(b"certificates");
+        let origin = hash_finder.find();
+
+        let certificate = hash_finder.certify(&origin).unwrap();
+        assert_eq!(
+            u32::from(certificate.difficulty_achieved),
+            hash_finder.check_difficulty(&origin)
+        );
+        assert_eq!(certificate.token_id, hash_finder.token_id(&origin))
+    }
+}
@@ -0,0 +1,427 @@
+//! Proofs bound to a random challenge issued by the server.
+//!
+//! Without a challenge any origin hash found in advance passes [`HashFinder::check`], so a client can mint
+//! proofs offline and replay them. With a [`Challenge`] the target hash is computed over the challenge followed by
+//! the origin hash, so the work can only start once the server has issued the challenge and is only valid for it.
+//!
+//! A challenge from [`Challenge::issue`] also carries the time it was issued and how long it is valid, so a server
+//! can refuse proofs for challenges that expired without keeping track of the challenges it issued. These times come
+//! back from the client, which can make up a challenge of its own: the server bounds the lifetime it accepts with the
+//! `max_ttl` of [`HashFinder::check_with_expiring_challenge`], and a stateless server must also check that it issued
+//! the challenge, by signing it with a [`ChallengeKey`](crate::ChallengeKey) and verifying the signature.
+
+use core::fmt;
+use core::str::FromStr;
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "std")]
+use rand_core::{OsRng, RngCore};
+
+use crate::{decode_origin_hash, Binding, HashFinder, PowError};
+
+/// `Challenge` is a random nonce a server issues to a client before it starts searching.
+///
+/// The server keeps the challenge, for instance in the session of the client, and sends it in its hexadecimal
+/// representation. The client finds an origin hash with [`HashFinder::find_with_challenge`] and the server verifies it
+/// with [`HashFinder::check_with_challenge`]. Every challenge should be accepted at most once.
+///
+/// # Example
+/// ```
+/// use pow_account::{Challenge, HashFinder};
+///
+/// // Server
+/// let challenge = Challenge::random();
+/// let sent = challenge.to_string();
+///
+/// // Client
+/// let received: Challenge = sent.parse().unwrap();
+/// let origin_hash = HashFinder::new(3).find_with_challenge(&received);
+///
+/// // Server
+/// assert!(HashFinder::new(3).check_with_challenge(&challenge, hex::encode(origin_hash)).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Challenge {
+    nonce: [u8; 32],
+}
+
+impl Challenge {
+    /// Returns a challenge drawn from the operating system random number generator
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        let mut nonce = [0u8; 32];
+        OsRng.fill_bytes(&mut nonce);
+        Challenge { nonce }
+    }
+
+    /// Returns a challenge that expires `ttl` after now
+    ///
+    /// The first 8 bytes of the challenge are the time it was issued and the next 4 bytes its lifetime, both in whole
+    /// seconds and big-endian, and the remaining 20 bytes are random. The times are part of the bytes the proof is
+    /// computed over, so they can't be changed without invalidating the proof. A lifetime is rounded down to whole
+    /// seconds and saturates at `u32::MAX` seconds.
+    ///
+    /// A stateless server verifies the proof with [`HashFinder::check_with_expiring_challenge`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics on targets without a system clock, such as `wasm32-unknown-unknown`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use pow_account::Challenge;
+    ///
+    /// let challenge = Challenge::issue(Duration::from_secs(60));
+    /// assert_eq!(challenge.expires_at(), challenge.issued_at() + 60);
+    /// assert!(challenge.validate().is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn issue(ttl: Duration) -> Self {
+        let ttl = u32::try_from(ttl.as_secs()).unwrap_or(u32::MAX);
+        let mut challenge = Challenge::random();
+        challenge.nonce[..8].copy_from_slice(&unix_time().to_be_bytes());
+        challenge.nonce[8..12].copy_from_slice(&ttl.to_be_bytes());
+        challenge
+    }
+
+    /// Returns a challenge with the given 32 bytes, for instance restored from storage
+    pub const fn from_bytes(nonce: [u8; 32]) -> Self {
+        Challenge { nonce }
+    }
+
+    /// Returns the 32 bytes of the challenge
+    pub fn bytes(&self) -> [u8; 32] {
+        self.nonce
+    }
+
+    /// Returns the time the challenge was issued in seconds since the Unix epoch
+    ///
+    /// This is only meaningful for a challenge from [`Challenge::issue`].
+    pub fn issued_at(&self) -> u64 {
+        let mut issued_at = [0u8; 8];
+        issued_at.copy_from_slice(&self.nonce[..8]);
+        u64::from_be_bytes(issued_at)
+    }
+
+    /// Returns the lifetime of the challenge in seconds
+    ///
+    /// This is only meaningful for a challenge from [`Challenge::issue`].
+    pub fn ttl(&self) -> u64 {
+        let mut ttl = [0u8; 4];
+        ttl.copy_from_slice(&self.nonce[8..12]);
+        u64::from(u32::from_be_bytes(ttl))
+    }
+
+    /// Returns the time the challenge expires in seconds since the Unix epoch
+    ///
+    /// This is only meaningful for a challenge from [`Challenge::issue`].
+    pub fn expires_at(&self) -> u64 {
+        self.issued_at().saturating_add(self.ttl())
+    }
+
+    /// Determines whether the lifetime of the challenge is at most `max_ttl`
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::LifetimeTooLong`] if the lifetime is longer than `max_ttl`.
+    pub fn validate_ttl(&self, max_ttl: Duration) -> Result<(), PowError> {
+        match self.ttl() > max_ttl.as_secs() {
+            true => Err(PowError::LifetimeTooLong {
+                ttl: self.ttl(),
+                max_ttl: max_ttl.as_secs(),
+            }),
+            false => Ok(()),
+        }
+    }
+
+    /// Determines whether the challenge is valid at `now`, in seconds since the Unix epoch
+    ///
+    /// A challenge is valid from the second it was issued until the second before it expires.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::IssuedInFuture`] if the challenge is issued after `now`, and
+    /// [`PowError::Expired`] if it expired at or before `now`. The bytes of a challenge that didn't come from
+    /// [`Challenge::issue`] are random, so it almost always fails with one of them.
+    pub fn validate_at(&self, now: u64) -> Result<(), PowError> {
+        if self.issued_at() > now {
+            return Err(PowError::IssuedInFuture {
+                issued_at: self.issued_at(),
+            });
+        }
+        if self.expires_at() <= now {
+            return Err(PowError::Expired {
+                expires_at: self.expires_at(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Determines whether the challenge is valid now, as with [`Challenge::validate_at`]
+    ///
+    /// # Errors
+    ///
+    /// This function returns the errors of [`Challenge::validate_at`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics on targets without a system clock, such as `wasm32-unknown-unknown`.
+    #[cfg(feature = "std")]
+    pub fn validate(&self) -> Result<(), PowError> {
+        self.validate_at(unix_time())
+    }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.nonce
+            .iter()
+            .try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl FromStr for Challenge {
+    type Err = PowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_origin_hash(s).map(Challenge::from_bytes)
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Finds an origin hash for a challenge issued by the server
+    ///
+    /// The target hash is computed over the challenge followed by the origin hash, so the origin hash only passes
+    /// [`HashFinder::check_with_challenge`] for the same challenge.
+    ///
+    /// # Parameters
+    ///
+    /// - `challenge`: The challenge received from the server.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    #[cfg(feature = "std")]
+    pub fn find_with_challenge(&self, challenge: &Challenge) -> [u8; 32] {
+        self.find_bound(Binding::Challenge, &challenge.nonce)
+    }
+
+    /// Determines whether a given origin hash was found for a challenge with [`HashFinder::find_with_challenge`].
+    ///
+    /// # Parameters
+    ///
+    /// - `challenge`: The challenge issued to the client.
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    pub fn check_with_challenge<S: AsRef<[u8]>>(
+        &self,
+        challenge: &Challenge,
+        origin_hash: S,
+    ) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+
+        Ok(self.meets_target(&self.bound_target_hash(
+            Binding::Challenge,
+            &challenge.nonce,
+            origin_hash_bytes,
+        )))
+    }
+
+    /// Determines whether a given origin hash was found for a challenge from [`Challenge::issue`] that is still valid
+    ///
+    /// This is [`HashFinder::check_with_challenge`] preceded by [`Challenge::validate_ttl`] and
+    /// [`Challenge::validate`], so proofs for expired challenges can't be stockpiled and replayed later. The times of
+    /// the challenge are chosen by whoever made it: without a check of its signature with
+    /// [`ChallengeKey::verify_signature`](crate::ChallengeKey::verify_signature), a client can make up challenges of
+    /// its own, so `max_ttl` should be the longest lifetime the server issues.
+    ///
+    /// # Parameters
+    ///
+    /// - `challenge`: The challenge issued to the client, as sent back by the client.
+    /// - `max_ttl`: The longest lifetime accepted.
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::LifetimeTooLong`] if the lifetime of the challenge is longer than `max_ttl`,
+    /// [`PowError::Expired`] or [`PowError::IssuedInFuture`] if the challenge is not valid now, and an error if the
+    /// provided hash string is not a valid hexadecimal representation.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use pow_account::{Challenge, HashFinder};
+    ///
+    /// let ttl = Duration::from_secs(60);
+    /// let challenge = Challenge::issue(ttl);
+    /// let origin_hash = HashFinder::new(3).find_with_challenge(&challenge);
+    ///
+    /// assert!(HashFinder::new(3)
+    ///     .check_with_expiring_challenge(&challenge, ttl, hex::encode(origin_hash))
+    ///     .unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn check_with_expiring_challenge<S: AsRef<[u8]>>(
+        &self,
+        challenge: &Challenge,
+        max_ttl: Duration,
+        origin_hash: S,
+    ) -> Result<bool, PowError> {
+        challenge.validate_ttl(max_ttl)?;
+        challenge.validate()?;
+        self.check_with_challenge(challenge, origin_hash)
+    }
+}
+
+/// Returns the current time in whole seconds since the Unix epoch.
+#[cfg(feature = "std")]
+pub(crate) fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn proof_is_only_valid_for_its_challenge() {
+        let hash_finder = HashFinder::new(3);
+        let challenge = Challenge::random();
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+
+        assert!(hash_finder
+            .check_with_challenge(&challenge, &origin_hash)
+            .unwrap());
+
+        // The target hash is 00000c0f… without a challenge and e7c2d748… with a challenge of zeros
+        let origin_hash = "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e";
+        assert!(HashFinder::new(5).check(origin_hash).unwrap());
+        assert!(!HashFinder::new(1)
+            .check_with_challenge(&Challenge::from_bytes([0u8; 32]), origin_hash)
+            .unwrap())
+    }
+
+    #[test]
+    fn challenge_round_trips_through_its_hexadecimal_representation() {
+        let challenge = Challenge::random();
+        let text = challenge.to_string();
+
+        assert_eq!(text.len(), 64);
+        assert_eq!(text, hex::encode(challenge.bytes()));
+        assert_eq!(text.parse(), Ok(challenge));
+        assert_ne!(Challenge::random(), challenge);
+
+        assert_eq!(
+            "abcd".parse::<Challenge>(),
+            Err(PowError::InvalidLength {
+                len: 4,
+                expected: 64
+            })
+        )
+    }
+
+    #[test]
+    fn issued_challenge_carries_its_lifetime() {
+        let challenge = Challenge::issue(Duration::from_secs(90));
+        let now = unix_time();
+
+        assert!(challenge.issued_at() <= now && now <= challenge.issued_at() + 1);
+        assert_eq!(challenge.expires_at(), challenge.issued_at() + 90);
+        assert_eq!(challenge.to_string().parse(), Ok(challenge));
+        assert_ne!(Challenge::issue(Duration::from_secs(90)), challenge);
+
+        let challenge = Challenge::issue(Duration::from_secs(u64::MAX));
+        assert_eq!(
+            challenge.expires_at() - challenge.issued_at(),
+            u64::from(u32::MAX)
+        )
+    }
+
+    #[test]
+    fn challenge_is_only_valid_during_its_lifetime() {
+        let mut nonce = [0u8; 32];
+        nonce[..8].copy_from_slice(&1_000u64.to_be_bytes());
+        nonce[8..12].copy_from_slice(&60u32.to_be_bytes());
+        let challenge = Challenge::from_bytes(nonce);
+
+        assert_eq!(
+            challenge.validate_at(999),
+            Err(PowError::IssuedInFuture { issued_at: 1_000 })
+        );
+        assert_eq!(challenge.validate_at(1_000), Ok(()));
+        assert_eq!(challenge.validate_at(1_059), Ok(()));
+        assert_eq!(
+            challenge.validate_at(1_060),
+            Err(PowError::Expired { expires_at: 1_060 })
+        );
+        assert!(challenge.validate().is_err())
+    }
+
+    #[test]
+    fn proof_for_an_expired_challenge_is_rejected() {
+        let hash_finder = HashFinder::new(2);
+
+        let ttl = Duration::from_secs(60);
+        let challenge = Challenge::issue(ttl);
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+        assert_eq!(
+            hash_finder.check_with_expiring_challenge(&challenge, ttl, &origin_hash),
+            Ok(true)
+        );
+
+        let challenge = Challenge::issue(Duration::ZERO);
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+        assert!(hash_finder
+            .check_with_challenge(&challenge, &origin_hash)
+            .unwrap());
+        assert_eq!(
+            hash_finder.check_with_expiring_challenge(&challenge, ttl, &origin_hash),
+            Err(PowError::Expired {
+                expires_at: challenge.issued_at()
+            })
+        )
+    }
+
+    #[test]
+    fn lifetimes_beyond_the_maximum_are_rejected() {
+        let hash_finder = HashFinder::new(2);
+        let max_ttl = Duration::from_secs(60);
+
+        let challenge = Challenge::issue(Duration::from_secs(61));
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+        assert_eq!(challenge.ttl(), 61);
+        assert_eq!(
+            hash_finder.check_with_expiring_challenge(&challenge, max_ttl, &origin_hash),
+            Err(PowError::LifetimeTooLong {
+                ttl: 61,
+                max_ttl: 60
+            })
+        );
+
+        // A challenge made up by the client, valid for the next 136 years
+        let mut nonce = Challenge::random().bytes();
+        nonce[..8].copy_from_slice(&unix_time().to_be_bytes());
+        nonce[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+        let challenge = Challenge::from_bytes(nonce);
+        assert_eq!(challenge.validate(), Ok(()));
+        assert!(matches!(
+            challenge.validate_ttl(max_ttl),
+            Err(PowError::LifetimeTooLong { .. })
+        ));
+
+        assert_eq!(Challenge::issue(max_ttl).validate_ttl(max_ttl), Ok(()))
+    }
+}
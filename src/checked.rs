@@ -0,0 +1,122 @@
+//! Origin hashes with a trailing checksum byte for transport integrity.
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::{HashFinder, PowError};
+
+/// Length of an origin hash followed by its checksum byte.
+pub const CHECKED_LEN: usize = 33;
+
+fn checksum(origin: &[u8; 32]) -> u8 {
+    origin.iter().fold(0u8, |checksum, byte| checksum ^ byte)
+}
+
+/// Appends a checksum byte to an origin hash
+///
+/// The checksum is the XOR of all bytes of the origin hash. It is cheap to compute and lets
+/// [`HashFinder::verify_checked`] reject tokens corrupted in transit before hashing them.
+///
+/// # Example
+///
+/// ```rust
+/// use pow_account::{encode_checked, HashFinder};
+///
+/// let hash_finder = HashFinder::new(3);
+/// let token = encode_checked(&hash_finder.find());
+///
+/// assert!(hash_finder.verify_checked(&token).unwrap());
+/// ```
+pub fn encode_checked(origin: &[u8; 32]) -> [u8; CHECKED_LEN] {
+    let mut token = [0u8; CHECKED_LEN];
+    token[..32].copy_from_slice(origin);
+    token[32] = checksum(origin);
+    token
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Determines whether a token created with [`encode_checked`] holds a valid origin hash.
+    ///
+    /// The checksum is validated first, so corrupted tokens are rejected without computing the target hash.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes`: The origin hash followed by its checksum byte.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Ok(true)` if the origin hash meets the requirement, otherwise
+    /// it returns `Ok(false)`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the token has the wrong length or if the checksum doesn't match.
+    pub fn verify_checked(&self, bytes: &[u8]) -> Result<bool, PowError> {
+        let token: &[u8; CHECKED_LEN] = bytes.try_into().map_err(|_| PowError::InvalidLength {
+            len: bytes.len(),
+            expected: CHECKED_LEN,
+        })?;
+
+        let mut origin = [0u8; 32];
+        origin.copy_from_slice(&token[..32]);
+
+        let expected = checksum(&origin);
+        if token[32] != expected {
+            return Err(PowError::ChecksumMismatch {
+                expected,
+                found: token[32],
+            });
+        }
+
+        Ok(self.meets_target(&self.target_hash(origin)))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn checked_token_is_verified() {
+        let origin: [u8; 32] =
+            hex::decode("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let token = encode_checked(&origin);
+
+        assert_eq!(&token[..32], &origin);
+        assert_eq!(HashFinder::new(3).verify_checked(&token), Ok(true));
+        assert_eq!(HashFinder::new(4).verify_checked(&token), Ok(false))
+    }
+
+    #[test]
+    fn corrupted_token_is_rejected() {
+        let mut token = encode_checked(&[0x5a; 32]);
+        token[7] ^= 0x01;
+
+        let err = HashFinder::new(3).verify_checked(&token).unwrap_err();
+        assert_eq!(
+            err,
+            PowError::ChecksumMismatch {
+                expected: 0x01,
+                found: 0x00
+            }
+        )
+    }
+
+    #[test]
+    fn token_with_wrong_length_is_rejected() {
+        let token = encode_checked(&[0x5a; 32]);
+
+        let err = HashFinder::new(3).verify_checked(&token[..32]).unwrap_err();
+        assert_eq!(
+            err,
+            PowError::InvalidLength {
+                len: 32,
+                expected: CHECKED_LEN
+            }
+        )
+    }
+}
@@ -0,0 +1,164 @@
+//! Proofs of work with asymmetric costs, based on hash collisions.
+//!
+//! Finding an origin hash for a [`HashFinder`](crate::HashFinder) takes as many hashes as the difficulty requires and
+//! verifying it takes one, but an attacker can spread the search over any number of cores with no memory at all. A
+//! [`CollisionFinder`] instead asks for two nonces whose hashes agree on their first bits, as in the Momentum
+//! puzzle. Finding them takes on the order of 2<sup>bits/2</sup> hashes and as many stored hashes in memory, by the
+//! birthday bound, while verifying them always takes two hashes.
+
+use std::collections::HashMap;
+
+use blake2::{Blake2s256, Digest};
+
+use crate::{Entropy, Finder};
+
+/// Largest number of leading bits two hashes can be required to share.
+pub const MAX_COLLISION_BITS: u32 = 64;
+
+/// `CollisionProof` is a seed and two distinct nonces whose hashes collide on the required bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CollisionProof {
+    /// The random seed both nonces are hashed with.
+    pub seed: [u8; 32],
+    /// The two nonces.
+    pub nonces: [u32; 2],
+}
+
+/// `CollisionFinder` finds and verifies [`CollisionProof`]s for a number of collision bits.
+///
+/// Each additional bit multiplies the expected work and memory of a search by about √2. Around 40 bits the search
+/// takes about a million hashes and tens of MiB, while verification still costs two hashes.
+///
+/// # Example
+/// ```
+/// use pow_account::CollisionFinder;
+///
+/// let finder = CollisionFinder::new(20);
+/// let proof = finder.find();
+///
+/// assert!(finder.verify(&proof));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionFinder {
+    bits: u32,
+}
+
+impl CollisionFinder {
+    /// Returns a CollisionFinder requiring the hashes of the two nonces to share their first `bits` bits
+    ///
+    /// Difficulties above [`MAX_COLLISION_BITS`] are reduced to it.
+    pub fn new(bits: u32) -> Self {
+        CollisionFinder {
+            bits: bits.min(MAX_COLLISION_BITS),
+        }
+    }
+
+    /// Returns the number of leading bits the two hashes have to share
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Finds two nonces whose hashes collide on the required bits
+    ///
+    /// Hashes are stored by their leading bits until one repeats. If the 2<sup>32</sup> nonces of a seed are
+    /// exhausted, which only happens at the highest difficulties, the search starts again with a new seed.
+    pub fn find(&self) -> CollisionProof {
+        loop {
+            let seed = Entropy::new().entropy;
+            let mut seen: HashMap<u64, u32> = HashMap::new();
+
+            for nonce in 0..=u32::MAX {
+                let prefix = self.prefix(&seed, nonce);
+                if let Some(&other) = seen.get(&prefix) {
+                    return CollisionProof {
+                        seed,
+                        nonces: [other, nonce],
+                    };
+                }
+                seen.insert(prefix, nonce);
+            }
+        }
+    }
+
+    /// Determines whether the nonces of a proof are distinct and their hashes collide on the required bits
+    pub fn verify(&self, proof: &CollisionProof) -> bool {
+        let [first, second] = proof.nonces;
+        first != second && self.prefix(&proof.seed, first) == self.prefix(&proof.seed, second)
+    }
+
+    /// Returns the first `bits` bits of the hash of a nonce, right-aligned.
+    fn prefix(&self, seed: &[u8; 32], nonce: u32) -> u64 {
+        if self.bits == 0 {
+            return 0;
+        }
+
+        let hash = Blake2s256::new()
+            .chain_update(seed)
+            .chain_update(nonce.to_le_bytes())
+            .finalize();
+        let mut leading = [0u8; 8];
+        leading.copy_from_slice(&hash[..8]);
+        u64::from_be_bytes(leading) >> (MAX_COLLISION_BITS - self.bits)
+    }
+}
+
+/// The proof is a [`CollisionProof`], found with [`CollisionFinder::find`] and verified with
+/// [`CollisionFinder::verify`].
+impl Finder for CollisionFinder {
+    type Proof = CollisionProof;
+
+    fn find_proof(&self) -> Self::Proof {
+        self.find()
+    }
+
+    fn verify_proof(&self, proof: &Self::Proof) -> bool {
+        self.verify(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn found_nonces_collide() {
+        for bits in [0, 1, 8, 24] {
+            let finder = CollisionFinder::new(bits);
+            let proof = finder.find();
+
+            assert!(finder.verify(&proof), "{bits}");
+            assert_ne!(proof.nonces[0], proof.nonces[1]);
+            assert_eq!(
+                finder.prefix(&proof.seed, proof.nonces[0]),
+                finder.prefix(&proof.seed, proof.nonces[1])
+            )
+        }
+    }
+
+    #[test]
+    fn tampered_proofs_are_rejected() {
+        let finder = CollisionFinder::new(20);
+        let proof = finder.find();
+
+        let [first, _] = proof.nonces;
+        assert!(!finder.verify(&CollisionProof {
+            nonces: [first, first],
+            ..proof
+        }));
+
+        let mut seed = proof.seed;
+        seed[0] ^= 1;
+        assert!(!finder.verify(&CollisionProof { seed, ..proof }))
+    }
+
+    #[test]
+    fn difficulty_is_capped_at_64_bits() {
+        assert_eq!(CollisionFinder::new(100), CollisionFinder::new(64));
+        assert_eq!(CollisionFinder::new(100).bits(), MAX_COLLISION_BITS);
+        assert_eq!(
+            CollisionFinder::new(1).prefix(&[0; 32], 1),
+            CollisionFinder::new(64).prefix(&[0; 32], 1) >> 63
+        )
+    }
+}
@@ -0,0 +1,173 @@
+//! Targets in the compact encoding of Bitcoin's `nBits` header field.
+//!
+//! A compact target takes 4 bytes: the most significant byte is the length in bytes of the target and the 3 others
+//! are its most significant bytes, so a target is `mantissa * 256^(exponent - 3)`. The high bit of the mantissa is a
+//! sign bit, which is never set for a valid target.
+
+use crate::{HashFinder, PowError};
+
+/// Sign bit of the mantissa of a compact target.
+const SIGN_BIT: u32 = 0x0080_0000;
+
+impl HashFinder {
+    /// Returns a HashFinder struct whose target is decoded from the compact `nBits` encoding
+    ///
+    /// Target hashes have to be strictly lower than the decoded target, where Bitcoin also accepts a block hash equal
+    /// to it, which makes the search harder by a single value out of `2^256`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::InvalidCompact`] if the sign bit is set on a non-zero target or if the
+    /// target doesn't fit in 256 bits.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::{HashFinder, PowError};
+    ///
+    /// // The difficulty of the Bitcoin genesis block
+    /// let hash_finder = HashFinder::from_compact(0x1d00ffff).unwrap();
+    /// assert_eq!(hash_finder.difficulty_bits(), 32);
+    /// assert_eq!(hash_finder.to_compact(), 0x1d00ffff);
+    ///
+    /// assert_eq!(
+    ///     HashFinder::from_compact(0x04923456),
+    ///     Err(PowError::InvalidCompact { compact: 0x04923456 })
+    /// );
+    /// ```
+    pub fn from_compact(compact: u32) -> Result<Self, PowError> {
+        let exponent = (compact >> 24) as usize;
+        let mantissa = compact & 0x007f_ffff;
+
+        // The mantissa bytes land at 32 - exponent and after: those past the end are shifted out, those before the
+        // start overflow
+        let mut target = [0u8; 32];
+        for (i, byte) in mantissa.to_be_bytes()[1..].iter().enumerate() {
+            match (32 + i).checked_sub(exponent) {
+                Some(index) if index < 32 => target[index] = *byte,
+                Some(_) => {}
+                None if *byte != 0 => return Err(PowError::InvalidCompact { compact }),
+                None => {}
+            }
+        }
+
+        // As in Bitcoin, the sign only matters for the bytes of the mantissa that remain
+        match compact & SIGN_BIT != 0 && target != [0u8; 32] {
+            true => Err(PowError::InvalidCompact { compact }),
+            false => Ok(HashFinder::from_target(target)),
+        }
+    }
+}
+
+impl<D> HashFinder<D> {
+    /// Returns the target in the compact `nBits` encoding
+    ///
+    /// Only the 3 most significant bytes of the target are kept, so the encoding rounds the target down and
+    /// [`HashFinder::from_compact`] restores it exactly only when its other bytes are zero. A HashFinder decoded from
+    /// a compact target is never easier than the original.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::with_bits(20);
+    /// let compact = hash_finder.to_compact();
+    ///
+    /// assert_eq!(compact, 0x1e0fffff);
+    /// assert!(HashFinder::from_compact(compact).unwrap().target() <= hash_finder.target());
+    /// ```
+    pub fn to_compact(&self) -> u32 {
+        let start = match self.target.iter().position(|&byte| byte != 0) {
+            Some(start) => start,
+            None => return 0,
+        };
+
+        let mut mantissa = [0u8; 4];
+        for (i, byte) in self.target[start..].iter().take(3).enumerate() {
+            mantissa[1 + i] = *byte;
+        }
+        let mut mantissa = u32::from_be_bytes(mantissa);
+        let mut exponent = (32 - start) as u32;
+
+        // A mantissa with its high bit set would read as negative, so it gives up its last byte for a zero
+        if mantissa & SIGN_BIT != 0 {
+            mantissa >>= 8;
+            exponent += 1;
+        }
+
+        exponent << 24 | mantissa
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+
+    fn target_of(compact: u32) -> Result<[u8; 32], PowError> {
+        HashFinder::from_compact(compact).map(|hash_finder| hash_finder.target())
+    }
+
+    #[test]
+    fn compact_targets_decode_like_bitcoin() {
+        let mut target = [0u8; 32];
+        target[31] = 0x12;
+        assert_eq!(target_of(0x01123456), Ok(target));
+
+        let mut target = [0u8; 32];
+        target[28..].copy_from_slice(&[0x92, 0x34, 0, 0]);
+        assert_eq!(target_of(0x05009234), Ok(target));
+
+        let mut target = [0u8; 32];
+        target[..3].copy_from_slice(&[0x12, 0x34, 0x56]);
+        assert_eq!(target_of(0x20123456), Ok(target));
+
+        let mut target = [0u8; 32];
+        target[..2].copy_from_slice(&[0xff, 0xff]);
+        assert_eq!(target_of(0x2100ffff), Ok(target));
+
+        assert_eq!(target_of(0), Ok([0; 32]));
+        assert_eq!(target_of(0x00123456), Ok([0; 32]));
+        assert_eq!(target_of(0x01803456), Ok([0; 32]));
+        assert_eq!(target_of(0x04800000), Ok([0; 32]))
+    }
+
+    #[test]
+    fn negative_and_overflowing_targets_are_rejected() {
+        for compact in [0x04923456, 0x01fedcba, 0x21010000, 0x22000100, 0xff123456] {
+            assert_eq!(
+                target_of(compact),
+                Err(PowError::InvalidCompact { compact }),
+                "{compact:#010x}"
+            )
+        }
+    }
+
+    #[test]
+    fn compact_targets_round_trip() {
+        for compact in [
+            0x1d00ffff, 0x1b0404cb, 0x05009234, 0x20123456, 0x2100ffff, 0,
+        ] {
+            assert_eq!(
+                HashFinder::from_compact(compact).unwrap().to_compact(),
+                compact,
+                "{compact:#010x}"
+            )
+        }
+        assert_eq!(
+            HashFinder::from_compact(0x01123456).unwrap().to_compact(),
+            0x01120000
+        )
+    }
+
+    #[test]
+    fn encoding_rounds_the_target_down() {
+        for bits in [0, 1, 7, 8, 20, 32, 255] {
+            let hash_finder = HashFinder::with_bits(bits);
+            let decoded = HashFinder::from_compact(hash_finder.to_compact()).unwrap();
+
+            assert!(decoded.target() <= hash_finder.target(), "{bits}");
+            assert_eq!(decoded.difficulty_bits(), bits, "{bits}")
+        }
+        assert_eq!(HashFinder::with_bits(0).to_compact(), 0x2100ffff)
+    }
+}
@@ -0,0 +1,95 @@
+//! Proofs bound to a message or an account identifier.
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::{decode_origin_hash, Binding, HashFinder, PowError};
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Finds an origin hash bound to a context, such as an account identifier
+    ///
+    /// The context is hashed once and its digest is mixed into the target hash, which is computed over
+    /// `H(context)` followed by the origin hash. The origin hash therefore only passes [`HashFinder::check_for`] with
+    /// the same context and can't be replayed for another account. The target hash also records that the digest is
+    /// the one of a context, so the proof doesn't pass [`HashFinder::check_with_prehash`] with
+    /// [`payload_prehash`](crate::payload_prehash)`(context)`.
+    ///
+    /// # Parameters
+    ///
+    /// - `context`: The bytes the proof is bound to.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::new(3).find_for(b"account-1");
+    ///
+    /// let result = HashFinder::new(3).check_for(b"account-1", hex::encode(origin_hash));
+    /// assert!(result.unwrap());
+    /// ```
+    pub fn find_for(&self, context: &[u8]) -> [u8; 32] {
+        self.find_bound(Binding::Context, &D::digest(context).into())
+    }
+
+    /// Determines whether a given origin hash was found for a context with [`HashFinder::find_for`].
+    ///
+    /// # Parameters
+    ///
+    /// - `context`: The bytes the proof is expected to be bound to.
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    pub fn check_for<S: AsRef<[u8]>>(
+        &self,
+        context: &[u8],
+        origin_hash: S,
+    ) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+        let context_hash: [u8; 32] = D::digest(context).into();
+
+        Ok(self.meets_target(&self.bound_target_hash(
+            Binding::Context,
+            &context_hash,
+            origin_hash_bytes,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn proof_is_only_valid_for_its_context() {
+        // The target hash is 0000a298… for account-1, c9d32d19… for account-2, ae0c4cf5… without a context and
+        // cdd263a6… for the pre-hashed payload account-1
+        let origin_hash = "d1607e816e83374d3d86daeea817c46f6451b5669623a9561b435a484df19879";
+        let hash_finder = HashFinder::new(4);
+
+        assert!(hash_finder.check_for(b"account-1", origin_hash).unwrap());
+        assert!(!HashFinder::new(1)
+            .check_for(b"account-2", origin_hash)
+            .unwrap());
+        assert!(!HashFinder::new(1).check(origin_hash).unwrap());
+        assert!(!HashFinder::new(1)
+            .check_with_prehash(&crate::payload_prehash(b"account-1"), origin_hash)
+            .unwrap())
+    }
+
+    #[test]
+    fn found_proof_passes_the_check_for_its_context() {
+        let hash_finder = HashFinder::new(3);
+        let origin_hash = hex::encode(hash_finder.find_for(b"account-1"));
+
+        assert!(hash_finder.check_for(b"account-1", &origin_hash).unwrap())
+    }
+}
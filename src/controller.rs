@@ -0,0 +1,235 @@
+//! Difficulty that adapts to the observed solve times and request rate.
+
+use std::time::{Duration, Instant};
+
+use crate::{Calibrator, HashFinder, MAX_DIFFICULTY_BITS};
+
+/// Weight of a new event in the moving averages of a [`DifficultyController`] when none is configured.
+pub const DEFAULT_SMOOTHING: f64 = 0.2;
+
+/// Shortest solve time taken into account, so that a zero duration doesn't imply an infinite hash rate.
+const MIN_SOLVE_SECONDS: f64 = 1e-6;
+
+/// `DifficultyController` chooses the difficulty of the next challenge from the events of the previous ones.
+///
+/// Two signals are tracked with exponential moving averages:
+///
+/// - Every solve reports the difficulty and how long the client took, which implies the hash rate of the clients.
+///   The controller picks the number of bits expected to take `target_solve_time` at that hash rate, as
+///   [`Calibrator::difficulty_for`] does, so clients with faster hardware get harder challenges.
+/// - Every validation marks the arrival of a valid proof. With a target rate configured, each doubling of the
+///   observed rate above it adds one bit, so a flood of requests makes every further request twice as expensive.
+///
+/// The result is always kept between the minimum and maximum number of bits. The controller only changes on events:
+/// once a flood stops, the rate falls back with the next validation.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use pow_account::DifficultyController;
+///
+/// let mut controller = DifficultyController::new(Duration::from_millis(500), 8, 24);
+/// assert_eq!(controller.bits(), 8);
+///
+/// // Clients solve 8 bits in a millisecond, about 256 000 hashes per second
+/// for _ in 0..20 {
+///     controller.record_solve(8, Duration::from_millis(1));
+/// }
+/// assert_eq!(controller.bits(), 17);
+///
+/// let hash_finder = controller.hash_finder();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyController {
+    target_solve_time: Duration,
+    target_rate: Option<f64>,
+    min_bits: u32,
+    max_bits: u32,
+    smoothing: f64,
+    log_hashrate: Option<f64>,
+    interval: Option<f64>,
+    last_validation: Option<Instant>,
+}
+
+impl DifficultyController {
+    /// Returns a DifficultyController aiming at `target_solve_time` and bounded by `min_bits` and `max_bits`
+    ///
+    /// Until the first solve is recorded the difficulty is `min_bits`. `max_bits` is raised to `min_bits` if it is
+    /// lower, and both are capped at [`MAX_DIFFICULTY_BITS`].
+    ///
+    /// # Parameters
+    ///
+    /// - `target_solve_time`: The expected time a client should spend on a challenge.
+    /// - `min_bits`: The lowest number of leading zero bits ever issued.
+    /// - `max_bits`: The highest number of leading zero bits ever issued.
+    pub fn new(target_solve_time: Duration, min_bits: u32, max_bits: u32) -> Self {
+        let min_bits = min_bits.min(MAX_DIFFICULTY_BITS);
+        DifficultyController {
+            target_solve_time,
+            target_rate: None,
+            min_bits,
+            max_bits: max_bits.clamp(min_bits, MAX_DIFFICULTY_BITS),
+            smoothing: DEFAULT_SMOOTHING,
+            log_hashrate: None,
+            interval: None,
+            last_validation: None,
+        }
+    }
+
+    /// Returns the DifficultyController raising the difficulty when more than `per_second` proofs per second are
+    /// validated
+    pub fn with_target_rate(self, per_second: f64) -> Self {
+        DifficultyController {
+            target_rate: Some(per_second),
+            ..self
+        }
+    }
+
+    /// Returns the DifficultyController with another weight of new events in its moving averages
+    ///
+    /// A weight close to 1 follows the last events closely, a weight close to 0 smooths over many of them. The
+    /// weight is clamped to `0.01..=1.0`.
+    pub fn with_smoothing(self, smoothing: f64) -> Self {
+        DifficultyController {
+            smoothing: smoothing.clamp(0.01, 1.0),
+            ..self
+        }
+    }
+
+    /// Records that a client found an origin hash for `bits` leading zero bits in `solve_time`
+    ///
+    /// The solve time is typically measured from the issue of the challenge to the submission of the proof.
+    pub fn record_solve(&mut self, bits: u32, solve_time: Duration) {
+        let log_hashrate = bits as f64 - solve_time.as_secs_f64().max(MIN_SOLVE_SECONDS).log2();
+        self.log_hashrate = Some(self.average(self.log_hashrate, log_hashrate));
+    }
+
+    /// Records that a valid proof was received at `at`
+    ///
+    /// Validations are expected in chronological order. The first one only starts the measurement of the rate.
+    pub fn record_validation(&mut self, at: Instant) {
+        if let Some(last) = self.last_validation {
+            let interval = at.saturating_duration_since(last).as_secs_f64();
+            self.interval = Some(self.average(self.interval, interval));
+        }
+        self.last_validation = Some(at);
+    }
+
+    /// Returns the hash rate of the clients implied by the recorded solves, in hashes per second
+    pub fn observed_hashrate(&self) -> Option<f64> {
+        self.log_hashrate.map(f64::exp2)
+    }
+
+    /// Returns the rate of the recorded validations, in proofs per second
+    pub fn observed_rate(&self) -> Option<f64> {
+        self.interval.map(|interval| 1.0 / interval)
+    }
+
+    /// Returns the number of leading zero bits to issue for the next challenge
+    pub fn bits(&self) -> u32 {
+        let solve_bits = match self.observed_hashrate() {
+            Some(hashrate) => Calibrator::difficulty_for(self.target_solve_time, hashrate),
+            None => self.min_bits,
+        };
+        let rate_bits = match (self.target_rate, self.observed_rate()) {
+            (Some(target_rate), Some(rate)) => (rate / target_rate).log2().round().max(0.0),
+            _ => 0.0,
+        };
+
+        (solve_bits as f64 + rate_bits).clamp(self.min_bits as f64, self.max_bits as f64) as u32
+    }
+
+    /// Returns the HashFinder to issue the next challenge with
+    pub fn hash_finder(&self) -> HashFinder {
+        HashFinder::with_bits(self.bits())
+    }
+
+    fn average(&self, average: Option<f64>, value: f64) -> f64 {
+        match average {
+            Some(average) => average + self.smoothing * (value - average),
+            None => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn difficulty_starts_at_the_minimum() {
+        let controller = DifficultyController::new(Duration::from_secs(1), 10, 20);
+
+        assert_eq!(controller.bits(), 10);
+        assert_eq!(controller.hash_finder(), HashFinder::with_bits(10));
+        assert_eq!(controller.observed_hashrate(), None);
+        assert_eq!(controller.observed_rate(), None)
+    }
+
+    #[test]
+    fn solve_times_follow_the_hash_rate_of_the_clients() {
+        let mut controller =
+            DifficultyController::new(Duration::from_secs(1), 0, 64).with_smoothing(1.0);
+
+        // 2^16 attempts in a 16th of a second is a million hashes per second
+        controller.record_solve(16, Duration::from_millis(62));
+        assert_eq!(controller.bits(), 20);
+
+        // Slower clients lower the difficulty
+        controller.record_solve(16, Duration::from_secs(4));
+        assert_eq!(controller.bits(), 14)
+    }
+
+    #[test]
+    fn moving_average_smooths_single_solves() {
+        let mut controller = DifficultyController::new(Duration::from_secs(1), 0, 64);
+        controller.record_solve(20, Duration::from_secs(1));
+        controller.record_solve(20, Duration::from_secs(1024));
+
+        // The single slow solve moves the estimate by a fifth of its 10 bits
+        assert_eq!(controller.bits(), 18)
+    }
+
+    #[test]
+    fn difficulty_is_bounded() {
+        let mut controller = DifficultyController::new(Duration::from_secs(1), 8, 12);
+        controller.record_solve(30, Duration::from_millis(1));
+        assert_eq!(controller.bits(), 12);
+
+        controller.record_solve(0, Duration::ZERO);
+        assert_eq!(controller.bits(), 12);
+
+        let mut controller = DifficultyController::new(Duration::from_secs(1), 8, 12);
+        controller.record_solve(0, Duration::from_secs(60));
+        assert_eq!(controller.bits(), 8);
+
+        assert_eq!(DifficultyController::new(Duration::ZERO, 9, 3).bits(), 9);
+        assert_eq!(
+            DifficultyController::new(Duration::ZERO, 300, 400).bits(),
+            MAX_DIFFICULTY_BITS
+        )
+    }
+
+    #[test]
+    fn request_floods_add_a_bit_per_doubling() {
+        let start = Instant::now();
+        let mut controller = DifficultyController::new(Duration::from_secs(1), 10, 30)
+            .with_target_rate(10.0)
+            .with_smoothing(1.0);
+
+        controller.record_validation(start);
+        controller.record_validation(start + Duration::from_millis(100));
+        assert_eq!(controller.bits(), 10);
+
+        // 80 validations per second are 3 doublings above the target
+        controller
+            .record_validation(start + Duration::from_millis(112) + Duration::from_micros(500));
+        assert_eq!(controller.bits(), 13);
+
+        // The rate falls back once the flood stops
+        controller.record_validation(start + Duration::from_secs(2));
+        assert!((controller.observed_rate().unwrap() - 0.53).abs() < 0.01);
+        assert_eq!(controller.bits(), 10)
+    }
+}
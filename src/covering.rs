@@ -0,0 +1,148 @@
+//! A single proof covering a batch of records.
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::{decode_origin_hash, Binding, HashFinder, PowError};
+
+const LEAF_TAG: u8 = 0x00;
+const NODE_TAG: u8 = 0x01;
+
+/// Computes the order-sensitive Merkle root of a list of records with the digest `D`.
+///
+/// Leaves are `H(0x00 || record)` and inner nodes are `H(0x01 || left || right)`. An odd node at the end of a level
+/// is promoted to the next level unchanged. The root of an empty list is the hash of the empty input.
+fn merkle_root<D: Digest<OutputSize = U32>>(records: &[&[u8]]) -> [u8; 32] {
+    if records.is_empty() {
+        return D::new().finalize().into();
+    }
+
+    let mut level: Vec<[u8; 32]> = records
+        .iter()
+        .map(|record| {
+            let mut hash = D::new();
+            hash.update([LEAF_TAG]);
+            hash.update(record);
+            hash.finalize().into()
+        })
+        .collect();
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut hash = D::new();
+                    hash.update([NODE_TAG]);
+                    hash.update(left);
+                    hash.update(right);
+                    hash.finalize().into()
+                }
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    level[0]
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Finds an origin hash that covers a whole batch of records
+    ///
+    /// The records are combined into an order-sensitive Merkle root, with leaves `H(0x00 || record)` and inner nodes
+    /// `H(0x01 || left || right)`, and the target hash is computed over the root followed by the origin hash. Changing, adding,
+    /// removing or reordering any record therefore invalidates the origin hash. Clients pay for one proof instead of
+    /// one per record, so the difficulty should be raised accordingly.
+    ///
+    /// # Parameters
+    ///
+    /// - `records`: The records covered by the proof, in order.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let records: [&[u8]; 3] = [b"alice", b"bob", b"carol"];
+    /// let origin_hash = HashFinder::new(3).find_covering_many(&records);
+    ///
+    /// let result = HashFinder::new(3).check_covering_many(&records, hex::encode(origin_hash));
+    /// assert!(result.unwrap());
+    /// ```
+    pub fn find_covering_many(&self, records: &[&[u8]]) -> [u8; 32] {
+        self.find_bound(Binding::Records, &merkle_root::<D>(records))
+    }
+
+    /// Determines whether a given origin hash was found for a batch of records with [`HashFinder::find_covering_many`].
+    ///
+    /// # Parameters
+    ///
+    /// - `records`: The records covered by the proof, in the same order as when it was found.
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    pub fn check_covering_many<S: AsRef<[u8]>>(
+        &self,
+        records: &[&[u8]],
+        origin_hash: S,
+    ) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+        let root = merkle_root::<D>(records);
+
+        Ok(self.meets_target(&self.bound_target_hash(Binding::Records, &root, origin_hash_bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use blake2::Blake2s256;
+
+    #[test]
+    fn merkle_root_is_order_sensitive() {
+        let records: [&[u8]; 3] = [b"alice", b"bob", b"carol"];
+        let reordered: [&[u8]; 3] = [b"bob", b"alice", b"carol"];
+
+        assert_eq!(
+            merkle_root::<Blake2s256>(&records),
+            merkle_root::<Blake2s256>(&records)
+        );
+        assert_ne!(
+            merkle_root::<Blake2s256>(&records),
+            merkle_root::<Blake2s256>(&reordered)
+        );
+        assert_ne!(
+            merkle_root::<Blake2s256>(&records),
+            merkle_root::<Blake2s256>(&records[..2])
+        );
+        assert_ne!(
+            merkle_root::<Blake2s256>(&[b"alice"]),
+            merkle_root::<Blake2s256>(&[])
+        )
+    }
+
+    #[test]
+    fn changing_a_record_invalidates_the_proof() {
+        let hash_finder = HashFinder::new(4);
+        let records: [&[u8]; 3] = [b"alice", b"bob", b"carol"];
+        let tampered: [&[u8]; 3] = [b"alice", b"bob", b"mallory"];
+
+        let origin_hash = hex::encode(hash_finder.find_covering_many(&records));
+
+        assert!(hash_finder
+            .check_covering_many(&records, origin_hash.clone())
+            .unwrap());
+        assert!(!hash_finder
+            .check_covering_many(&tampered, origin_hash)
+            .unwrap())
+    }
+}
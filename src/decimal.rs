@@ -0,0 +1,93 @@
+//! Difficulty expressed as leading zeros of the target hash rendered in base 10.
+
+use crate::HashFinder;
+
+/// Number of decimal digits needed to render any 256-bit value, since `2^256 - 1` is about `1.16 * 10^77`.
+pub const DECIMAL_DIGITS: u8 = 78;
+
+/// Returns `10^exponent` as a big-endian 256-bit value, saturating at `2^256 - 1`.
+fn power_of_ten(exponent: u8) -> [u8; 32] {
+    let mut value = [0u8; 32];
+    value[31] = 1;
+
+    for _ in 0..exponent {
+        let mut carry = 0u16;
+        for byte in value.iter_mut().rev() {
+            let product = *byte as u16 * 10 + carry;
+            *byte = product as u8;
+            carry = product >> 8;
+        }
+        if carry != 0 {
+            return [0xff; 32];
+        }
+    }
+
+    value
+}
+
+impl HashFinder {
+    /// Returns a HashFinder struct requiring a number of leading zeros of the target hash rendered in base 10
+    ///
+    /// The target hash is read as a 256-bit unsigned integer and written with [`DECIMAL_DIGITS`] decimal digits,
+    /// padded with zeros on the left. It has at least `n` leading decimal zeros exactly when its value is lower
+    /// than `10^(78 - n)`, which becomes the internal 256-bit target. Origin hashes are found with
+    /// [`HashFinder::find`] and validated with [`HashFinder::check`] as usual.
+    ///
+    /// Every decimal zero makes the search about 10 times harder. `n = 0` accepts any target hash and `n >= 78`
+    /// accepts only a target hash of zero.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::from_decimal_zeros(3);
+    /// let origin_hash = hash_finder.find();
+    ///
+    /// assert!(hash_finder.check(hex::encode(origin_hash)).unwrap());
+    /// ```
+    pub fn from_decimal_zeros(n: u8) -> Self {
+        HashFinder::with_target(power_of_ten(DECIMAL_DIGITS.saturating_sub(n)))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn powers_of_ten_are_computed_in_256_bits() {
+        let mut thousand = [0u8; 32];
+        thousand[30..].copy_from_slice(&1000u16.to_be_bytes());
+        assert_eq!(power_of_ten(3), thousand);
+
+        let mut ten_to_the_30 = [0u8; 32];
+        ten_to_the_30[16..].copy_from_slice(&10u128.pow(30).to_be_bytes());
+        assert_eq!(power_of_ten(30), ten_to_the_30);
+
+        assert!(power_of_ten(77) < [0xff; 32]);
+        assert_eq!(power_of_ten(78), [0xff; 32])
+    }
+
+    #[test]
+    fn decimal_zeros_of_the_target_hash_are_enforced() {
+        // The target hash is 00000c0fd80d3af2a3c41459ebcd9a73adbacd8c442b229e75afad56c2c08f2c,
+        // which is 000000083248... when written with 78 decimal digits
+        let hash = String::from("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e");
+
+        assert!(HashFinder::from_decimal_zeros(7)
+            .check(hash.clone())
+            .unwrap());
+        assert!(!HashFinder::from_decimal_zeros(8).check(hash).unwrap())
+    }
+
+    #[test]
+    fn extreme_decimal_zeros_accept_everything_or_nothing() {
+        assert_eq!(HashFinder::from_decimal_zeros(0).target, [0xff; 32]);
+
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(HashFinder::from_decimal_zeros(78).target, one);
+        assert_eq!(HashFinder::from_decimal_zeros(200).target, one)
+    }
+}
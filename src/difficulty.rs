@@ -0,0 +1,374 @@
+//! Validation and measurement of difficulties in leading zero bits.
+
+use core::fmt;
+use core::str::FromStr;
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2s256, Digest};
+
+use crate::{leading_zero_bits, Entropy, HashFinder, PowError};
+
+/// Maximum number of leading zero bits a difficulty can require.
+///
+/// At 256 bits the target would be zero, and no target hash is strictly lower than zero.
+pub const MAX_DIFFICULTY_BITS: u32 = 255;
+
+/// Text written before the number of bits by the [`Display`](fmt::Display) implementation of [`HashFinder`].
+const DIFFICULTY_PREFIX: &str = "pow-difficulty=";
+
+/// Text written after the number of bits by the [`Display`](fmt::Display) implementation of [`HashFinder`].
+const DIFFICULTY_SUFFIX: &str = "bit";
+
+impl HashFinder {
+    /// Returns a HashFinder struct with a specified number of target leading zeros, rejecting impossible difficulties
+    ///
+    /// Each leading zero is a hexadecimal digit of 4 bits. At 64 leading zeros the target would be zero and no origin
+    /// hash could ever be found, so at most 63 leading zeros are accepted.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if `leading_zeros` requires more than [`MAX_DIFFICULTY_BITS`] bits.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::{HashFinder, PowError};
+    ///
+    /// assert_eq!(HashFinder::try_new(4), Ok(HashFinder::new(4)));
+    /// assert_eq!(HashFinder::try_new(65), Err(PowError::TooManyBits { bits: 260 }));
+    ///
+    /// ```
+    pub fn try_new(leading_zeros: u8) -> Result<Self, PowError> {
+        let bits = 4 * u32::from(leading_zeros);
+        match bits <= MAX_DIFFICULTY_BITS {
+            true => Ok(HashFinder::with_bits(bits)),
+            false => Err(PowError::TooManyBits { bits }),
+        }
+    }
+
+    /// Returns the number of leading zero bits of the target hash produced by an origin hash
+    ///
+    /// Unlike [`HashFinder::check_difficulty`], no HashFinder is needed and the result is counted in bits rather
+    /// than in hexadecimal digits, so origin hashes can be compared by the work they actually achieved. The target
+    /// hash is computed with the default digest and version.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::with_bits(10).find();
+    /// assert!(HashFinder::difficulty_of(&origin_hash) >= 10);
+    ///
+    /// ```
+    pub fn difficulty_of(origin_hash: &[u8; 32]) -> u32 {
+        leading_zero_bits(&Entropy::from(*origin_hash).hash::<Blake2s256>())
+    }
+}
+
+impl<D> HashFinder<D> {
+    /// Returns the number of leading zero bits required of target hashes
+    ///
+    /// This recovers the difficulty given to [`HashFinder::with_bits`], or four times the number of leading zeros given
+    /// to [`HashFinder::new`]. For a target set with [`HashFinder::from_target`], it is the number of leading zero bits
+    /// of the highest target hash that passes, `target - 1`, so every passing target hash has at least that many. The
+    /// targets 1 and 0, which only the zero hash or no hash at all passes, report [`MAX_DIFFICULTY_BITS`].
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// assert_eq!(HashFinder::with_bits(18).difficulty_bits(), 18);
+    /// assert_eq!(HashFinder::new(5).difficulty_bits(), 20);
+    ///
+    /// // Target hashes below 2^248 start with 8 zero bits
+    /// let mut target = [0u8; 32];
+    /// target[0] = 0x01;
+    /// assert_eq!(HashFinder::from_target(target).difficulty_bits(), 8);
+    ///
+    /// ```
+    pub fn difficulty_bits(&self) -> u32 {
+        if self.target == [0u8; 32] {
+            return MAX_DIFFICULTY_BITS;
+        }
+
+        let mut highest = self.target;
+        for byte in highest.iter_mut().rev() {
+            let (decremented, borrow) = byte.overflowing_sub(1);
+            *byte = decremented;
+            if !borrow {
+                break;
+            }
+        }
+        leading_zero_bits(&highest).min(MAX_DIFFICULTY_BITS)
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Determines whether an origin hash clears a minimum difficulty and reports the difficulty it achieved
+    ///
+    /// The target hash is computed with the settings of this HashFinder, such as its digest, version, rounds and
+    /// domain, but its target is ignored in favour of `min_bits`. This allows a proof to be gated and graded in one
+    /// pass, for instance to reward proofs that exceed the minimum.
+    ///
+    /// # Parameters
+    ///
+    /// - `origin_hash`: The origin hash to check.
+    /// - `min_bits`: The number of leading zero bits the target hash needs at least.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Some` number of leading zero bits of the target hash if it is at least `min_bits`,
+    /// or `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::TooManyBits`] if `min_bits` exceeds [`MAX_DIFFICULTY_BITS`].
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::with_bits(10).find();
+    /// let achieved = HashFinder::default().check_min(&origin_hash, 10).unwrap();
+    ///
+    /// assert!(achieved >= Some(10));
+    ///
+    /// ```
+    pub fn check_min(
+        &self,
+        origin_hash: &[u8; 32],
+        min_bits: u32,
+    ) -> Result<Option<u32>, PowError> {
+        if min_bits > MAX_DIFFICULTY_BITS {
+            return Err(PowError::TooManyBits { bits: min_bits });
+        }
+
+        let achieved = leading_zero_bits(&self.target_hash(*origin_hash));
+        match achieved >= min_bits {
+            true => Ok(Some(achieved)),
+            false => Ok(None),
+        }
+    }
+}
+
+/// Writes the difficulty as `pow-difficulty=<bits>bit`, where `bits` is [`HashFinder::difficulty_bits`].
+///
+/// Only the difficulty is written, so parsing the text back with [`FromStr`] restores a HashFinder with the default
+/// settings at that difficulty.
+///
+/// # Example
+/// ```
+/// use pow_account::HashFinder;
+///
+/// assert_eq!(HashFinder::new(6).to_string(), "pow-difficulty=24bit");
+///
+/// ```
+impl<D> fmt::Display for HashFinder<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{DIFFICULTY_PREFIX}{}{DIFFICULTY_SUFFIX}",
+            self.difficulty_bits()
+        )
+    }
+}
+
+/// Parses a difficulty written as `pow-difficulty=<bits>bit` into a HashFinder, as with [`HashFinder::with_bits`].
+///
+/// # Errors
+///
+/// Parsing returns [`PowError::InvalidFormat`] if the text doesn't follow the format and [`PowError::TooManyBits`] if
+/// the number of bits exceeds [`MAX_DIFFICULTY_BITS`].
+///
+/// # Example
+/// ```
+/// use pow_account::HashFinder;
+///
+/// let hash_finder: HashFinder = "pow-difficulty=24bit".parse().unwrap();
+/// assert_eq!(hash_finder, HashFinder::new(6));
+///
+/// ```
+impl FromStr for HashFinder {
+    type Err = PowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = s
+            .strip_prefix(DIFFICULTY_PREFIX)
+            .and_then(|s| s.strip_suffix(DIFFICULTY_SUFFIX))
+            .filter(|bits| bits.bytes().all(|c| c.is_ascii_digit()))
+            .and_then(|bits| bits.parse::<u32>().ok())
+            .ok_or(PowError::InvalidFormat)?;
+
+        match bits <= MAX_DIFFICULTY_BITS {
+            true => Ok(HashFinder::with_bits(bits)),
+            false => Err(PowError::TooManyBits { bits }),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn difficulty_bits_are_recovered_from_the_target() {
+        for bits in [0, 1, 7, 20, 254, 255] {
+            assert_eq!(HashFinder::with_bits(bits).difficulty_bits(), bits)
+        }
+
+        // Target hashes below 0x0040… are at most 0x003f…
+        let mut target = [0u8; 32];
+        target[1] = 0x40;
+        assert_eq!(HashFinder::from_target(target).difficulty_bits(), 10);
+
+        // Target hashes below a power of two have all the zero bits of its exponent
+        let mut target = [0u8; 32];
+        target[0] = 0x01;
+        assert_eq!(HashFinder::from_target(target).difficulty_bits(), 8);
+        target[31] = 0x01;
+        assert_eq!(HashFinder::from_target(target).difficulty_bits(), 7);
+
+        let mut target = [0u8; 32];
+        assert_eq!(HashFinder::from_target(target).difficulty_bits(), 255);
+        target[31] = 0x01;
+        assert_eq!(HashFinder::from_target(target).difficulty_bits(), 255);
+        target[31] = 0x02;
+        assert_eq!(HashFinder::from_target(target).difficulty_bits(), 255);
+        target[31] = 0x03;
+        assert_eq!(HashFinder::from_target(target).difficulty_bits(), 254)
+    }
+
+    #[test]
+    fn difficulty_round_trips_through_its_text() {
+        for hash_finder in [
+            HashFinder::new(6),
+            HashFinder::with_bits(0),
+            HashFinder::new(64),
+        ] {
+            assert_eq!(hash_finder.to_string().parse(), Ok(hash_finder))
+        }
+        assert_eq!(
+            HashFinder::with_bits(13).to_string(),
+            "pow-difficulty=13bit"
+        )
+    }
+
+    #[test]
+    fn malformed_difficulty_text_is_rejected() {
+        for text in [
+            "",
+            "pow-difficulty=",
+            "pow-difficulty=bit",
+            "pow-difficulty=24",
+            "pow-difficulty=+24bit",
+            "pow-difficulty=24 bit",
+            "pow-difficulty=24bits",
+            "difficulty=24bit",
+            " pow-difficulty=24bit",
+            "pow-difficulty=99999999999bit",
+        ] {
+            assert_eq!(
+                text.parse::<HashFinder>(),
+                Err(PowError::InvalidFormat),
+                "{text}"
+            );
+        }
+
+        assert_eq!(
+            "pow-difficulty=256bit".parse::<HashFinder>(),
+            Err(PowError::TooManyBits { bits: 256 })
+        );
+        assert_eq!(
+            "pow-difficulty=255bit".parse::<HashFinder>(),
+            Ok(HashFinder::with_bits(255))
+        )
+    }
+
+    #[test]
+    fn minimum_check_reports_the_achieved_difficulty() {
+        // The target hash starts with 20 zero bits
+        let origin_hash = crate::decode_origin_hash(
+            "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e",
+        )
+        .unwrap();
+        let hash_finder = HashFinder::default();
+
+        assert_eq!(hash_finder.check_min(&origin_hash, 8), Ok(Some(20)));
+        assert_eq!(hash_finder.check_min(&origin_hash, 20), Ok(Some(20)));
+        assert_eq!(hash_finder.check_min(&origin_hash, 21), Ok(None));
+        assert_eq!(
+            hash_finder.check_min(&origin_hash, 256),
+            Err(PowError::TooManyBits { bits: 256 })
+        );
+        assert_eq!(hash_finder.check_min(&origin_hash, 255), Ok(None));
+        assert_eq!(
+            HashFinder::with_version(5, 2).check_min(&origin_hash, 8),
+            Ok(None)
+        )
+    }
+
+    #[test]
+    fn difficulties_up_to_255_bits_are_accepted() {
+        let mut target = [0u8; 32];
+        target[31] = 0x0f;
+        assert_eq!(HashFinder::try_new(63).unwrap().target, target);
+
+        // The hardest difficulty still has a target hash below its target: zero
+        target[31] = 0x01;
+        assert_eq!(HashFinder::with_bits(MAX_DIFFICULTY_BITS).target, target);
+        assert!(HashFinder::with_bits(MAX_DIFFICULTY_BITS).meets_target(&[0u8; 32]))
+    }
+
+    #[test]
+    fn difficulties_of_256_bits_and_more_are_rejected() {
+        assert_eq!(
+            HashFinder::try_new(64),
+            Err(PowError::TooManyBits { bits: 256 })
+        );
+        assert_eq!(
+            HashFinder::try_new(65),
+            Err(PowError::TooManyBits { bits: 260 })
+        );
+        assert_eq!(
+            HashFinder::try_new(u8::MAX),
+            Err(PowError::TooManyBits { bits: 1020 })
+        );
+        assert_eq!(
+            HashFinder::try_new(65).unwrap_err().to_string(),
+            "Difficulty of 260 bits exceeds the maximum of 255"
+        )
+    }
+
+    #[test]
+    fn difficulty_of_counts_leading_zero_bits() {
+        let known_vectors = [
+            // The target hash is 7478… and starts with the bits 0111
+            (
+                "c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa",
+                1,
+            ),
+            // The target hash is 000d4cbf… and starts with the bits 0000 0000 0000 1101
+            (
+                "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4",
+                12,
+            ),
+            // The target hash is 00000c0f… and starts with the bits 0000 0000 0000 0000 0000 1100
+            (
+                "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e",
+                20,
+            ),
+        ];
+
+        for (origin_hash_hex, bits) in known_vectors {
+            let origin_hash: [u8; 32] = hex::decode(origin_hash_hex).unwrap().try_into().unwrap();
+            assert_eq!(HashFinder::difficulty_of(&origin_hash), bits)
+        }
+    }
+
+    #[test]
+    fn new_saturates_at_255_bits() {
+        assert_eq!(HashFinder::new(64), HashFinder::with_bits(255));
+        assert_eq!(HashFinder::new(u8::MAX), HashFinder::with_bits(255));
+        assert_eq!(HashFinder::with_bits(256), HashFinder::with_bits(255))
+    }
+}
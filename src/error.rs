@@ -0,0 +1,205 @@
+//! The error type shared by the fallible APIs of the crate.
+
+use core::fmt;
+
+use crate::{DEFAULT_ROUNDS, MAX_DIFFICULTY_BITS};
+
+/// The error type for checking origin hashes and creating a [`HashFinder`](crate::HashFinder).
+///
+/// It is also exported as [`pow_account::Error`](Error).
+///
+/// # Example
+/// ```
+/// use pow_account::HashFinder;
+///
+/// fn verify_request(origin_hash: &str) -> Result<bool, pow_account::Error> {
+///     let accepted = HashFinder::new(3).check(origin_hash)?;
+///     Ok(accepted)
+/// }
+///
+/// assert!(verify_request("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4").unwrap());
+/// assert!(verify_request("not a hash").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum PowError {
+    /// The input is not a valid hexadecimal representation.
+    InvalidHex(hex::FromHexError),
+    /// The input is not exactly `expected` characters or bytes long, such as the 64 hexadecimal characters of an
+    /// origin hash or the [`CHECKED_LEN`](crate::CHECKED_LEN) bytes of a checked token.
+    InvalidLength { len: usize, expected: usize },
+    /// The checksum byte does not match the origin hash.
+    ChecksumMismatch { expected: u8, found: u8 },
+    /// The requested number of leading zero bits exceeds [`MAX_DIFFICULTY_BITS`].
+    TooManyBits { bits: u32 },
+    /// The requested number of hashing rounds is below [`DEFAULT_ROUNDS`](crate::DEFAULT_ROUNDS).
+    TooFewRounds { rounds: u32 },
+    /// The compact `nBits` encoding of a target is negative or doesn't fit in 256 bits.
+    InvalidCompact { compact: u32 },
+    /// The input doesn't follow the expected textual format, such as `pow-difficulty=24bit` for a difficulty.
+    InvalidFormat,
+    /// The challenge expired at `expires_at`, in seconds since the Unix epoch.
+    Expired { expires_at: u64 },
+    /// The challenge claims to be issued at `issued_at`, in seconds since the Unix epoch, which is still to come.
+    IssuedInFuture { issued_at: u64 },
+    /// The challenge claims a lifetime of `ttl` seconds, longer than the `max_ttl` seconds the server allows.
+    LifetimeTooLong { ttl: u64, max_ttl: u64 },
+    /// The signature of a challenge doesn't match the key, difficulty and client it is verified for.
+    InvalidSignature,
+    /// The proof was already accepted by [`HashFinder::check_and_consume`](crate::HashFinder::check_and_consume).
+    AlreadyUsed,
+    /// The [`SolutionStore`](crate::SolutionStore) that records the accepted proofs can't be reached.
+    StoreUnavailable,
+    /// The memory cost or number of iterations of a [`MemoryHardFinder`](crate::MemoryHardFinder) is out of the
+    /// range Argon2 accepts.
+    InvalidCost,
+    /// Reading the input failed.
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+/// The error type of the crate, an alias of [`PowError`].
+pub type Error = PowError;
+
+impl fmt::Display for PowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PowError::InvalidHex(err) => write!(f, "{err}"),
+            PowError::InvalidLength { len, expected } => {
+                write!(f, "Invalid input length {len}, expected {expected}")
+            }
+            PowError::ChecksumMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Checksum mismatch, expected {expected:#04x}, found {found:#04x}"
+                )
+            }
+            PowError::TooManyBits { bits } => {
+                write!(
+                    f,
+                    "Difficulty of {bits} bits exceeds the maximum of {MAX_DIFFICULTY_BITS}"
+                )
+            }
+            PowError::TooFewRounds { rounds } => {
+                write!(
+                    f,
+                    "{rounds} hashing rounds are below the minimum of {DEFAULT_ROUNDS}"
+                )
+            }
+            PowError::InvalidCompact { compact } => {
+                write!(f, "Compact target {compact:#010x} is negative or overflows")
+            }
+            PowError::InvalidFormat => write!(f, "Input doesn't follow the expected format"),
+            PowError::Expired { expires_at } => {
+                write!(f, "Challenge expired at {expires_at}")
+            }
+            PowError::IssuedInFuture { issued_at } => {
+                write!(f, "Challenge is issued in the future at {issued_at}")
+            }
+            PowError::LifetimeTooLong { ttl, max_ttl } => {
+                write!(
+                    f,
+                    "Challenge lifetime of {ttl}s exceeds the maximum of {max_ttl}s"
+                )
+            }
+            PowError::InvalidSignature => write!(f, "Challenge signature doesn't match"),
+            PowError::AlreadyUsed => write!(f, "Proof was already used"),
+            PowError::StoreUnavailable => write!(f, "Solution store is unavailable"),
+            PowError::InvalidCost => write!(f, "Argon2 cost parameters are out of range"),
+            #[cfg(feature = "std")]
+            PowError::Io(kind) => write!(f, "Failed to read the input: {kind}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PowError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PowError::InvalidHex(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<hex::FromHexError> for PowError {
+    fn from(err: hex::FromHexError) -> Self {
+        PowError::InvalidHex(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for PowError {
+    fn from(err: std::io::Error) -> Self {
+        PowError::Io(err.kind())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+    use crate::{encode_checked, HashFinder, CHECKED_LEN};
+
+    #[test]
+    fn each_bad_input_maps_to_its_variant() {
+        let hash = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+
+        match HashFinder::new(3).check(hash.replace('a', "+")) {
+            Err(PowError::InvalidHex(hex::FromHexError::InvalidHexCharacter {
+                c: '+', ..
+            })) => {}
+            result => panic!("unexpected {result:?}"),
+        }
+        match HashFinder::new(3).check(&hash[..62]) {
+            Err(PowError::InvalidLength {
+                len: 62,
+                expected: 64,
+            }) => {}
+            result => panic!("unexpected {result:?}"),
+        }
+        match HashFinder::new(3).verify_checked(&[0u8; 32]) {
+            Err(PowError::InvalidLength {
+                len: 32,
+                expected: CHECKED_LEN,
+            }) => {}
+            result => panic!("unexpected {result:?}"),
+        }
+
+        let mut token = encode_checked(&[0x5a; 32]);
+        token[0] ^= 0x01;
+        match HashFinder::new(3).verify_checked(&token) {
+            Err(PowError::ChecksumMismatch { .. }) => {}
+            result => panic!("unexpected {result:?}"),
+        }
+        match HashFinder::try_new(65) {
+            Err(PowError::TooManyBits { bits: 260 }) => {}
+            result => panic!("unexpected {result:?}"),
+        }
+    }
+
+    #[test]
+    fn hex_errors_convert_with_the_question_mark_operator() {
+        fn decode(input: &str) -> Result<[u8; 2], PowError> {
+            let mut bytes = [0u8; 2];
+            hex::decode_to_slice(input, &mut bytes)?;
+            Ok(bytes)
+        }
+
+        assert_eq!(decode("0aff"), Ok([0x0a, 0xff]));
+        assert_eq!(
+            decode("0g00"),
+            Err(PowError::InvalidHex(
+                hex::FromHexError::InvalidHexCharacter { c: 'g', index: 1 }
+            ))
+        );
+        assert_eq!(
+            PowError::InvalidLength {
+                len: 62,
+                expected: 64
+            }
+            .to_string(),
+            "Invalid input length 62, expected 64"
+        )
+    }
+}
@@ -0,0 +1,48 @@
+//! A common interface to the proof-of-work schemes of the crate.
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::HashFinder;
+
+/// `Finder` is a proof-of-work scheme: finding a proof is expensive and verifying it is cheap.
+///
+/// It is implemented by [`HashFinder`], by [`CollisionFinder`](crate::CollisionFinder), whose verification costs two
+/// hashes however much work the proof took, and with the `argon2` feature by `MemoryHardFinder`. Code that only needs
+/// to find and verify proofs can be written once for all of them.
+///
+/// # Example
+/// ```
+/// use pow_account::{CollisionFinder, Finder, HashFinder};
+///
+/// fn round_trip<F: Finder>(finder: &F) -> bool {
+///     let proof = finder.find_proof();
+///     finder.verify_proof(&proof)
+/// }
+///
+/// assert!(round_trip(&HashFinder::new(3)));
+/// assert!(round_trip(&CollisionFinder::new(16)));
+/// ```
+pub trait Finder {
+    /// The proof found by [`Finder::find_proof`], such as an origin hash.
+    type Proof;
+
+    /// Finds a proof that meets the difficulty of the scheme
+    fn find_proof(&self) -> Self::Proof;
+
+    /// Determines whether a proof meets the difficulty of the scheme
+    fn verify_proof(&self, proof: &Self::Proof) -> bool;
+}
+
+/// The proof is the origin hash of [`HashFinder::find`], verified with [`HashFinder::check_bytes`].
+impl<D: Digest<OutputSize = U32>> Finder for HashFinder<D> {
+    type Proof = [u8; 32];
+
+    fn find_proof(&self) -> Self::Proof {
+        self.find()
+    }
+
+    fn verify_proof(&self, proof: &Self::Proof) -> bool {
+        self.check_bytes(proof)
+    }
+}
@@ -0,0 +1,313 @@
+//! GPU search with a compute shader, enabled with the `gpu` feature.
+//!
+//! A [`GpuFinder`] runs the search of a [`HashFinder`] as a `wgpu` compute shader, in batches of [`GPU_BATCH`]
+//! candidates, on any adapter `wgpu` finds through Vulkan, Metal, DirectX 12 or OpenGL. As in the search of the `simd`
+//! feature, every candidate is a base drawn once XORed with a counter and is used as the origin hash directly, so each
+//! attempt is a single Blake2s hash. The origin hashes are checked on the CPU before they are returned. Without an
+//! adapter, the search runs on the CPU instead.
+
+use std::sync::{mpsc, Mutex, MutexGuard};
+
+use rand_core::{OsRng, RngCore};
+use wgpu::util::DeviceExt;
+
+use crate::{Entropy, HashFinder, DEFAULT_ROUNDS, DEFAULT_VERSION};
+
+/// Number of candidates hashed by every dispatch of the compute shader.
+pub const GPU_BATCH: u32 = WORKGROUP_SIZE * WORKGROUPS;
+
+/// Number of invocations of a workgroup, as declared by the shader.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Number of workgroups of every dispatch.
+const WORKGROUPS: u32 = 4096;
+
+/// Index reported by the shader when no candidate of a batch meets the target.
+const NOT_FOUND: u32 = u32::MAX;
+
+/// `GpuFinder` finds origin hashes for a [`HashFinder`] on the GPU when one is available.
+///
+/// Only the default Blake2s construction runs on the GPU: a HashFinder with a version or a domain tag is searched on
+/// the CPU with [`HashFinder::find_counter`], as is every HashFinder when no adapter was found. The origin hashes are
+/// the same kind either way and pass [`HashFinder::check`].
+///
+/// Creating a GpuFinder compiles the shader, so it should be created once and reused. Searches from several threads
+/// share the device and run one after another.
+///
+/// # Example
+/// ```
+/// use pow_account::{GpuFinder, HashFinder};
+///
+/// let hash_finder = HashFinder::new(3);
+/// let gpu_finder = GpuFinder::new(hash_finder);
+///
+/// let origin_hashes = gpu_finder.find_many(4);
+/// assert!(origin_hashes.iter().all(|origin_hash| hash_finder.check_bytes(origin_hash)));
+/// ```
+#[derive(Debug)]
+pub struct GpuFinder {
+    hash_finder: HashFinder,
+    gpu: Option<Mutex<Gpu>>,
+}
+
+#[derive(Debug)]
+struct Gpu {
+    adapter_name: String,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    params: wgpu::Buffer,
+    found: wgpu::Buffer,
+    readback: wgpu::Buffer,
+}
+
+impl GpuFinder {
+    /// Returns a GpuFinder for `hash_finder` on the first adapter available, or on the CPU if there is none
+    ///
+    /// The adapter is chosen by `wgpu` with a preference for high performance, and the backends can be restricted
+    /// with the `WGPU_BACKEND` environment variable.
+    pub fn new(hash_finder: HashFinder) -> Self {
+        let supported = hash_finder.version == DEFAULT_VERSION && hash_finder.domain.is_none();
+        GpuFinder {
+            hash_finder,
+            gpu: supported.then(Gpu::new).flatten().map(Mutex::new),
+        }
+    }
+
+    /// Returns the HashFinder the origin hashes are found for
+    pub fn hash_finder(&self) -> &HashFinder {
+        &self.hash_finder
+    }
+
+    /// Returns the name of the adapter the search runs on, or `None` if it runs on the CPU
+    pub fn adapter_name(&self) -> Option<String> {
+        self.gpu.as_ref().map(|gpu| lock(gpu).adapter_name.clone())
+    }
+
+    /// Finds an origin hash whose target hash starts with the required leading zeros
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    pub fn find(&self) -> [u8; 32] {
+        let gpu = match &self.gpu {
+            Some(gpu) => gpu,
+            None => return self.hash_finder.find_counter(),
+        };
+
+        let mut base = [0u8; 32];
+        OsRng.fill_bytes(&mut base);
+        let gpu = lock(gpu);
+
+        (0..=u64::MAX / GPU_BATCH as u64)
+            .find_map(|batch| self.batch_attempt(&gpu, base, batch * GPU_BATCH as u64))
+            .expect("no origin hash found in 2^64 attempts")
+    }
+
+    /// Finds `count` origin hashes, for instance to mint proofs in advance
+    pub fn find_many(&self, count: usize) -> Vec<[u8; 32]> {
+        (0..count).map(|_| self.find()).collect()
+    }
+
+    fn batch_attempt(&self, gpu: &Gpu, base: [u8; 32], start: u64) -> Option<[u8; 32]> {
+        let index = gpu.search(
+            &base,
+            &self.hash_finder.target,
+            start,
+            self.hash_finder.rounds.saturating_sub(DEFAULT_ROUNDS),
+        )?;
+
+        let origin_hash = Entropy::from_counter(base, start + index as u64).entropy;
+        self.hash_finder
+            .check_bytes(&origin_hash)
+            .then_some(origin_hash)
+    }
+}
+
+fn lock(gpu: &Mutex<Gpu>) -> MutexGuard<'_, Gpu> {
+    gpu.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Gpu {
+    fn new() -> Option<Self> {
+        let instance =
+            wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle_from_env());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        }))
+        .ok()?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).ok()?;
+
+        let module = device.create_shader_module(wgpu::include_wgsl!("gpu.wgsl"));
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("pow-account search"),
+            layout: None,
+            module: &module,
+            entry_point: Some("search"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("pow-account params"),
+            size: PARAMS_LEN as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let found = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("pow-account found"),
+            contents: &NOT_FOUND.to_le_bytes(),
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+        });
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("pow-account readback"),
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("pow-account bindings"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: found.as_entire_binding(),
+                },
+            ],
+        });
+
+        Some(Gpu {
+            adapter_name: adapter.get_info().name,
+            device,
+            queue,
+            pipeline,
+            bind_group,
+            params,
+            found,
+            readback,
+        })
+    }
+
+    /// Runs a batch from the counter `start` and returns the lowest index of the batch that meets the target.
+    fn search(
+        &self,
+        base: &[u8; 32],
+        target: &[u8; 32],
+        start: u64,
+        extra_rounds: u32,
+    ) -> Option<u32> {
+        self.queue
+            .write_buffer(&self.params, 0, &params(base, target, start, extra_rounds));
+        self.queue
+            .write_buffer(&self.found, 0, &NOT_FOUND.to_le_bytes());
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.dispatch_workgroups(WORKGROUPS, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&self.found, 0, &self.readback, 0, 4);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = mpsc::channel();
+        let slice = self.readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("the device was lost");
+        receiver
+            .recv()
+            .expect("the mapping callback runs before the poll returns")
+            .expect("the readback buffer can be mapped");
+
+        let index = {
+            let bytes = slice.get_mapped_range().expect("the buffer is mapped");
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        };
+        self.readback.unmap();
+
+        (index != NOT_FOUND).then_some(index)
+    }
+}
+
+/// Size of the parameters of the shader: the base and the target as 8 words each, the start and the extra rounds.
+const PARAMS_LEN: usize = 80;
+
+/// Lays out the parameters of the shader, with the base in little-endian and the target in big-endian words.
+fn params(base: &[u8; 32], target: &[u8; 32], start: u64, extra_rounds: u32) -> [u8; PARAMS_LEN] {
+    let mut params = [0u8; PARAMS_LEN];
+    params[..32].copy_from_slice(base);
+    for (word, bytes) in params[32..64]
+        .chunks_exact_mut(4)
+        .zip(target.chunks_exact(4))
+    {
+        let big_endian = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        word.copy_from_slice(&big_endian.to_le_bytes());
+    }
+    params[64..68].copy_from_slice(&(start as u32).to_le_bytes());
+    params[68..72].copy_from_slice(&((start >> 32) as u32).to_le_bytes());
+    params[72..76].copy_from_slice(&extra_rounds.to_le_bytes());
+    params
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn found_hash_passes_the_check() {
+        for hash_finder in [HashFinder::new(4), HashFinder::new(2).with_rounds(4)] {
+            let origin_hash = GpuFinder::new(hash_finder).find();
+            assert!(hash_finder.check_bytes(&origin_hash), "{hash_finder:?}")
+        }
+    }
+
+    #[test]
+    fn unsupported_constructions_run_on_the_cpu() {
+        let hash_finder = HashFinder::new(2).with_domain(b"gpu");
+        let gpu_finder = GpuFinder::new(hash_finder);
+
+        assert_eq!(gpu_finder.adapter_name(), None);
+        assert!(hash_finder.check_bytes(&gpu_finder.find()));
+        assert_eq!(gpu_finder.hash_finder(), &hash_finder)
+    }
+
+    #[test]
+    fn gpu_reports_the_first_hash_below_the_target() {
+        let gpu_finder = GpuFinder::new(HashFinder::new(0));
+        let Some(gpu) = &gpu_finder.gpu else {
+            return;
+        };
+        let gpu = lock(gpu);
+        let base = [0x5a; 32];
+
+        // Every hash is below the maximal target, and none below the zero target
+        assert_eq!(gpu.search(&base, &[0xff; 32], 7, 0), Some(0));
+        assert_eq!(gpu.search(&base, &[0; 32], 7, 0), None);
+
+        // The reported candidate is the first one the CPU accepts
+        let hash_finder = HashFinder::new(2);
+        let index = gpu.search(&base, &hash_finder.target, u32::MAX as u64, 0);
+        let expected = (0..GPU_BATCH).find(|&index| {
+            hash_finder
+                .check_bytes(&Entropy::from_counter(base, u32::MAX as u64 + index as u64).entropy)
+        });
+        assert_eq!(index, expected)
+    }
+}
@@ -0,0 +1,114 @@
+// Search of GpuFinder: every invocation derives a candidate origin hash from the base and its counter, computes its
+// target hash with Blake2s and reports the lowest invocation whose target hash is below the target.
+
+struct Params {
+    // The base entropy as little-endian words, the layout Blake2s reads its input in.
+    base: array<u32, 8>,
+    // The target as big-endian words, so that words compare like the bytes they hold.
+    target_words: array<u32, 8>,
+    start_lo: u32,
+    start_hi: u32,
+    extra_rounds: u32,
+    padding: u32,
+}
+
+@group(0) @binding(0) var<storage, read> params: Params;
+@group(0) @binding(1) var<storage, read_write> found: atomic<u32>;
+
+const IV = array<u32, 8>(
+    0x6A09E667u, 0xBB67AE85u, 0x3C6EF372u, 0xA54FF53Au,
+    0x510E527Fu, 0x9B05688Cu, 0x1F83D9ABu, 0x5BE0CD19u,
+);
+
+var<private> SIGMA: array<array<u32, 16>, 10> = array<array<u32, 16>, 10>(
+    array<u32, 16>(0u, 1u, 2u, 3u, 4u, 5u, 6u, 7u, 8u, 9u, 10u, 11u, 12u, 13u, 14u, 15u),
+    array<u32, 16>(14u, 10u, 4u, 8u, 9u, 15u, 13u, 6u, 1u, 12u, 0u, 2u, 11u, 7u, 5u, 3u),
+    array<u32, 16>(11u, 8u, 12u, 0u, 5u, 2u, 15u, 13u, 10u, 14u, 3u, 6u, 7u, 1u, 9u, 4u),
+    array<u32, 16>(7u, 9u, 3u, 1u, 13u, 12u, 11u, 14u, 2u, 6u, 5u, 10u, 4u, 0u, 15u, 8u),
+    array<u32, 16>(9u, 0u, 5u, 7u, 2u, 4u, 10u, 15u, 14u, 1u, 11u, 12u, 6u, 8u, 3u, 13u),
+    array<u32, 16>(2u, 12u, 6u, 10u, 0u, 11u, 8u, 3u, 4u, 13u, 7u, 5u, 15u, 14u, 1u, 9u),
+    array<u32, 16>(12u, 5u, 1u, 15u, 14u, 13u, 4u, 10u, 0u, 7u, 6u, 3u, 9u, 2u, 8u, 11u),
+    array<u32, 16>(13u, 11u, 7u, 14u, 12u, 1u, 3u, 9u, 5u, 0u, 15u, 4u, 8u, 6u, 2u, 10u),
+    array<u32, 16>(6u, 15u, 14u, 9u, 11u, 3u, 0u, 8u, 12u, 2u, 13u, 7u, 1u, 4u, 10u, 5u),
+    array<u32, 16>(10u, 2u, 8u, 4u, 7u, 6u, 1u, 5u, 15u, 11u, 9u, 14u, 3u, 12u, 13u, 0u),
+);
+
+fn rotr(x: u32, n: u32) -> u32 {
+    return (x >> n) | (x << (32u - n));
+}
+
+fn bswap(x: u32) -> u32 {
+    return (x << 24u) | ((x & 0xFF00u) << 8u) | ((x >> 8u) & 0xFF00u) | (x >> 24u);
+}
+
+fn g(v: ptr<function, array<u32, 16>>, a: u32, b: u32, c: u32, d: u32, x: u32, y: u32) {
+    (*v)[a] = (*v)[a] + (*v)[b] + x;
+    (*v)[d] = rotr((*v)[d] ^ (*v)[a], 16u);
+    (*v)[c] = (*v)[c] + (*v)[d];
+    (*v)[b] = rotr((*v)[b] ^ (*v)[c], 12u);
+    (*v)[a] = (*v)[a] + (*v)[b] + y;
+    (*v)[d] = rotr((*v)[d] ^ (*v)[a], 8u);
+    (*v)[c] = (*v)[c] + (*v)[d];
+    (*v)[b] = rotr((*v)[b] ^ (*v)[c], 7u);
+}
+
+// Blake2s-256 of a 32-byte input: a single block of the input followed by 32 zero bytes.
+fn blake2s(input: array<u32, 8>) -> array<u32, 8> {
+    var m: array<u32, 16>;
+    var h = IV;
+    var v: array<u32, 16>;
+    h[0] ^= 0x01010020u;
+    for (var i = 0u; i < 8u; i++) {
+        m[i] = input[i];
+        v[i] = h[i];
+        v[i + 8u] = IV[i];
+    }
+    // The counter is the 32 bytes of input and the block is the last one
+    v[12] ^= 32u;
+    v[14] = ~v[14];
+
+    for (var r = 0u; r < 10u; r++) {
+        let s = SIGMA[r];
+        g(&v, 0u, 4u, 8u, 12u, m[s[0]], m[s[1]]);
+        g(&v, 1u, 5u, 9u, 13u, m[s[2]], m[s[3]]);
+        g(&v, 2u, 6u, 10u, 14u, m[s[4]], m[s[5]]);
+        g(&v, 3u, 7u, 11u, 15u, m[s[6]], m[s[7]]);
+        g(&v, 0u, 5u, 10u, 15u, m[s[8]], m[s[9]]);
+        g(&v, 1u, 6u, 11u, 12u, m[s[10]], m[s[11]]);
+        g(&v, 2u, 7u, 8u, 13u, m[s[12]], m[s[13]]);
+        g(&v, 3u, 4u, 9u, 14u, m[s[14]], m[s[15]]);
+    }
+
+    for (var i = 0u; i < 8u; i++) {
+        h[i] ^= v[i] ^ v[i + 8u];
+    }
+    return h;
+}
+
+@compute @workgroup_size(64)
+fn search(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    let lo = params.start_lo + index;
+    let hi = params.start_hi + select(0u, 1u, lo < params.start_lo);
+
+    // The counter is XORed into the last 8 bytes of the base in big-endian order
+    var origin_hash = params.base;
+    origin_hash[6] ^= bswap(hi);
+    origin_hash[7] ^= bswap(lo);
+
+    var target_hash = blake2s(origin_hash);
+    for (var round = 0u; round < params.extra_rounds; round++) {
+        target_hash = blake2s(target_hash);
+    }
+
+    for (var i = 0u; i < 8u; i++) {
+        let word = bswap(target_hash[i]);
+        if (word < params.target_words[i]) {
+            atomicMin(&found, index);
+            return;
+        }
+        if (word > params.target_words[i]) {
+            return;
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! Hashcash version 1 stamps, enabled with the `hashcash` feature.
+//!
+//! A stamp is the text `1:bits:date:resource:ext:rand:counter`, and it is valid if the SHA-1 digest of the whole text
+//! starts with at least `bits` zero bits. Stamps minted here are accepted by other Hashcash implementations and the
+//! other way around. This module only checks the work and the resource; the date and the double spending of stamps
+//! are left to the caller, as in other Hashcash implementations.
+//!
+//! # Example
+//! ```
+//! use pow_account::hashcash::Stamp;
+//!
+//! let stamp = Stamp::mint("alice@example.com", 12).unwrap();
+//! let text = stamp.to_string();
+//!
+//! let received: Stamp = text.parse().unwrap();
+//! assert!(received.check("alice@example.com", 12));
+//! ```
+
+use core::fmt;
+use core::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use blake2::Digest;
+use rand_core::{OsRng, RngCore};
+use sha1::Sha1;
+
+use crate::{leading_zero_bits, HashFinder, PowError};
+
+/// Characters of the `rand` and `counter` fields, which are base64.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Number of base64 characters of the `rand` field of a minted stamp, which hold 96 random bits.
+const RAND_LEN: usize = 16;
+
+/// `Stamp` is a parsed or minted Hashcash version 1 stamp.
+///
+/// Its [`Display`](fmt::Display) implementation writes the text the digest is computed over, and its [`FromStr`]
+/// implementation parses it back.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stamp {
+    /// The number of leading zero bits the stamp claims.
+    pub bits: u32,
+    /// The date the stamp was minted, as `YYMMDD`, `YYMMDDhhmm` or `YYMMDDhhmmss` in UTC.
+    pub date: String,
+    /// The resource the stamp is bound to, such as an email address.
+    pub resource: String,
+    /// The extension field, which is usually empty.
+    pub ext: String,
+    /// The random salt, in base64.
+    pub rand: String,
+    /// The counter found by the search, in base64.
+    pub counter: String,
+}
+
+impl Stamp {
+    /// Mints a stamp with SHA-1 for `resource`, dated today
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::InvalidFormat`] if `resource` contains a colon and
+    /// [`PowError::TooManyBits`] if `bits` exceeds the 160 bits of a SHA-1 digest.
+    pub fn mint(resource: &str, bits: u32) -> Result<Self, PowError> {
+        Stamp::mint_with_digest::<Sha1>(resource, bits, &today())
+    }
+
+    /// Mints a stamp with any digest for `resource`, dated `date`
+    ///
+    /// Both sides have to agree on the digest, since Hashcash implementations only use SHA-1.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::InvalidFormat`] if `resource` contains a colon or `date` is not 6, 10 or
+    /// 12 digits, and [`PowError::TooManyBits`] if `bits` exceeds the output of the digest.
+    pub fn mint_with_digest<H: Digest + Clone>(
+        resource: &str,
+        bits: u32,
+        date: &str,
+    ) -> Result<Self, PowError> {
+        if bits > 8 * <H as Digest>::output_size() as u32 {
+            return Err(PowError::TooManyBits { bits });
+        }
+        if resource.contains(':') || !is_date(date) {
+            return Err(PowError::InvalidFormat);
+        }
+
+        let mut random = [0u8; RAND_LEN];
+        OsRng.fill_bytes(&mut random);
+        let rand: String = random
+            .iter()
+            .map(|byte| char::from(BASE64_ALPHABET[usize::from(byte >> 2)]))
+            .collect();
+
+        let mut stamp = Stamp {
+            bits,
+            date: String::from(date),
+            resource: String::from(resource),
+            ext: String::new(),
+            rand,
+            counter: String::new(),
+        };
+
+        let prefix = H::new().chain_update(stamp.to_string());
+        let mut counter = String::new();
+        for attempt in 0u64.. {
+            counter.clear();
+            push_base64(&mut counter, attempt);
+            if leading_zero_bits(&prefix.clone().chain_update(&counter).finalize()) >= bits {
+                break;
+            }
+        }
+
+        stamp.counter = counter;
+        Ok(stamp)
+    }
+
+    /// Returns the number of leading zero bits of the SHA-1 digest of the stamp
+    pub fn achieved_bits(&self) -> u32 {
+        self.achieved_bits_with_digest::<Sha1>()
+    }
+
+    /// Returns the number of leading zero bits of the digest of the stamp with any digest
+    pub fn achieved_bits_with_digest<H: Digest>(&self) -> u32 {
+        leading_zero_bits(&H::digest(self.to_string()))
+    }
+
+    /// Determines whether the stamp is a valid SHA-1 stamp for `resource` worth at least `required_bits`
+    ///
+    /// The stamp has to claim at least `required_bits` and its digest has to start with at least the bits it
+    /// claims, as Hashcash requires.
+    pub fn check(&self, resource: &str, required_bits: u32) -> bool {
+        self.resource == resource && self.bits >= required_bits && self.achieved_bits() >= self.bits
+    }
+}
+
+impl fmt::Display for Stamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "1:{}:{}:{}:{}:{}:",
+            self.bits, self.date, self.resource, self.ext, self.rand
+        )?;
+        f.write_str(&self.counter)
+    }
+}
+
+/// Parses a stamp of the form `1:bits:date:resource:ext:rand:counter`.
+///
+/// # Errors
+///
+/// Parsing returns [`PowError::InvalidFormat`] if the text doesn't have the seven fields of a version 1 stamp, the
+/// number of bits is not a decimal number or the date is not 6, 10 or 12 digits.
+impl FromStr for Stamp {
+    type Err = PowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split(':').collect();
+        let [version, bits, date, resource, ext, rand, counter] = fields[..] else {
+            return Err(PowError::InvalidFormat);
+        };
+
+        if version != "1" || bits.is_empty() || !bits.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(PowError::InvalidFormat);
+        }
+        if !is_date(date) {
+            return Err(PowError::InvalidFormat);
+        }
+
+        Ok(Stamp {
+            bits: bits.parse().map_err(|_| PowError::InvalidFormat)?,
+            date: String::from(date),
+            resource: String::from(resource),
+            ext: String::from(ext),
+            rand: String::from(rand),
+            counter: String::from(counter),
+        })
+    }
+}
+
+impl<D> HashFinder<D> {
+    /// Mints a SHA-1 Hashcash stamp for `resource` at the difficulty of this HashFinder
+    ///
+    /// Only the difficulty is used, as [`HashFinder::difficulty_bits`]; the digest and the other settings don't apply
+    /// to Hashcash.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the errors of [`Stamp::mint`].
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(3);
+    /// let stamp = hash_finder.mint_hashcash("alice@example.com").unwrap().to_string();
+    ///
+    /// assert!(hash_finder.check_hashcash(&stamp, "alice@example.com").unwrap());
+    /// ```
+    pub fn mint_hashcash(&self, resource: &str) -> Result<Stamp, PowError> {
+        Stamp::mint(resource, self.difficulty_bits())
+    }
+
+    /// Determines whether a SHA-1 Hashcash stamp for `resource` meets the difficulty of this HashFinder
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::InvalidFormat`] if `stamp` is not a version 1 stamp.
+    pub fn check_hashcash(&self, stamp: &str, resource: &str) -> Result<bool, PowError> {
+        Ok(stamp
+            .parse::<Stamp>()?
+            .check(resource, self.difficulty_bits()))
+    }
+}
+
+/// Appends `value` in base64 digits, most significant first.
+fn push_base64(counter: &mut String, value: u64) {
+    let digits = (64 - value.leading_zeros()).div_ceil(6).max(1);
+    for digit in (0..digits).rev() {
+        let index = (value >> (6 * digit)) & 0x3f;
+        counter.push(char::from(BASE64_ALPHABET[index as usize]));
+    }
+}
+
+fn is_date(date: &str) -> bool {
+    matches!(date.len(), 6 | 10 | 12) && date.bytes().all(|c| c.is_ascii_digit())
+}
+
+/// Returns the current UTC date as `YYMMDD`.
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+    let (year, month, day) = civil_from_days(days);
+    format!("{:02}{month:02}{day:02}", year % 100)
+}
+
+/// Converts a number of days since 1970-01-01 to a year, month and day of the Gregorian calendar.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn known_stamps_are_valid() {
+        // The SHA-1 digests are 00000a4a… and 00000b7c…, which start with 20 zero bits
+        for text in [
+            "1:20:060408:adam@cypherspace.org::1QTjaYd7niiQA/sc:ePa",
+            "1:20:1303030600:adam@cypherspace.org::McMybZIhxKXu57jd:ckvi",
+        ] {
+            let stamp: Stamp = text.parse().unwrap();
+            assert_eq!(stamp.to_string(), text);
+            assert_eq!(stamp.achieved_bits(), 20);
+            assert!(stamp.check("adam@cypherspace.org", 20));
+            assert!(!stamp.check("adam@cypherspace.org", 21));
+            assert!(!stamp.check("eve@cypherspace.org", 20))
+        }
+    }
+
+    #[test]
+    fn stamp_claiming_more_than_its_work_is_invalid() {
+        let text = "1:24:060408:adam@cypherspace.org::1QTjaYd7niiQA/sc:ePa";
+        let stamp: Stamp = text.parse().unwrap();
+
+        assert!(!stamp.check("adam@cypherspace.org", 20));
+        assert_eq!(
+            HashFinder::with_bits(20).check_hashcash(text, "adam@cypherspace.org"),
+            Ok(false)
+        )
+    }
+
+    #[test]
+    fn minted_stamp_round_trips() {
+        let stamp = Stamp::mint("alice@example.com", 10).unwrap();
+        assert_eq!(stamp.date, today());
+        assert_eq!(stamp.rand.len(), RAND_LEN);
+        assert!(stamp.achieved_bits() >= 10);
+
+        let text = stamp.to_string();
+        assert!(text.starts_with("1:10:"));
+        assert_eq!(text.parse(), Ok(stamp))
+    }
+
+    #[test]
+    fn stamp_can_use_another_digest() {
+        let stamp = Stamp::mint_with_digest::<sha2::Sha256>("alice", 10, "260101").unwrap();
+        assert!(stamp.achieved_bits_with_digest::<sha2::Sha256>() >= 10);
+
+        assert_eq!(
+            Stamp::mint_with_digest::<Sha1>("alice", 161, "260101"),
+            Err(PowError::TooManyBits { bits: 161 })
+        )
+    }
+
+    #[test]
+    fn malformed_stamps_and_resources_are_rejected() {
+        for text in [
+            "",
+            "1:20:060408:adam@cypherspace.org::1QTjaYd7niiQA/sc",
+            "0:20:060408:adam@cypherspace.org::1QTjaYd7niiQA/sc:ePa",
+            "1:2x:060408:adam@cypherspace.org::1QTjaYd7niiQA/sc:ePa",
+            "1::060408:adam@cypherspace.org::1QTjaYd7niiQA/sc:ePa",
+            "1:20:0604:adam@cypherspace.org::1QTjaYd7niiQA/sc:ePa",
+            "1:20:060408:adam:cypherspace.org::1QTjaYd7niiQA/sc:ePa",
+        ] {
+            assert_eq!(
+                text.parse::<Stamp>(),
+                Err(PowError::InvalidFormat),
+                "{text}"
+            )
+        }
+
+        assert_eq!(Stamp::mint("a:b", 1), Err(PowError::InvalidFormat));
+        assert_eq!(
+            Stamp::mint_with_digest::<Sha1>("alice", 1, "2601"),
+            Err(PowError::InvalidFormat)
+        )
+    }
+
+    #[test]
+    fn counter_is_written_in_base64_digits() {
+        for (value, digits) in [(0, "A"), (63, "/"), (64, "BA"), (4095, "//"), (4096, "BAA")] {
+            let mut counter = String::new();
+            push_base64(&mut counter, value);
+            assert_eq!(counter, digits)
+        }
+    }
+
+    #[test]
+    fn days_are_converted_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_055), (2000, 4, 8));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(civil_from_days(20_454), (2026, 1, 1))
+    }
+}
@@ -0,0 +1,172 @@
+//! Verification result shaped after HTTP response semantics.
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::{decode_origin_hash, target_difficulty, HashFinder};
+
+/// The result of [`HashFinder::check_http`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckHttp {
+    /// The origin hash meets the requirement. Maps to `200 OK`.
+    Accepted {
+        /// The number of leading zeros of the target hash.
+        difficulty: u8,
+    },
+    /// The origin hash is well-formed but its target hash doesn't have enough leading zeros. Maps to `403 Forbidden`.
+    InsufficientWork {
+        /// The number of leading zeros required by the HashFinder, rounded up to whole hexadecimal digits for
+        /// difficulties set in bits.
+        required: u8,
+        /// The number of leading zeros of the target hash.
+        achieved: u8,
+    },
+    /// The origin hash is not a valid hexadecimal representation of 32 bytes. Maps to `400 Bad Request`.
+    Malformed {
+        /// The description of the decoding error.
+        reason: String,
+    },
+}
+
+impl CheckHttp {
+    /// Returns the HTTP status code matching the result
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let result = HashFinder::new(4).check_http("not a hash");
+    /// assert_eq!(result.status_code(), 400);
+    /// ```
+    pub fn status_code(&self) -> u16 {
+        match self {
+            CheckHttp::Accepted { .. } => 200,
+            CheckHttp::InsufficientWork { .. } => 403,
+            CheckHttp::Malformed { .. } => 400,
+        }
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Parses, validates and measures an origin hash in one call
+    ///
+    /// # Parameters
+    ///
+    /// - `origin_hex`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Returns
+    ///
+    /// This function returns [`CheckHttp::Accepted`] with the achieved number of leading zeros if the hash meets
+    /// the requirement, [`CheckHttp::InsufficientWork`] with the required and achieved number of leading zeros
+    /// if it doesn't, and [`CheckHttp::Malformed`] if the input string is not a valid hexadecimal representation.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::{CheckHttp, HashFinder};
+    ///
+    /// let origin_hash = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+    /// let result = HashFinder::new(4).check_http(origin_hash);
+    ///
+    /// assert_eq!(result, CheckHttp::InsufficientWork { required: 4, achieved: 3 });
+    /// ```
+    pub fn check_http(&self, origin_hex: &str) -> CheckHttp {
+        let origin_hash = match decode_origin_hash(origin_hex) {
+            Ok(origin_hash) => origin_hash,
+            Err(err) => {
+                return CheckHttp::Malformed {
+                    reason: err.to_string(),
+                }
+            }
+        };
+
+        let target_hash = self.target_hash(origin_hash);
+        let achieved = target_difficulty(&target_hash) as u8;
+        match self.meets_target(&target_hash) {
+            true => CheckHttp::Accepted {
+                difficulty: achieved,
+            },
+            false => CheckHttp::InsufficientWork {
+                required: self.difficulty_bits().div_ceil(4) as u8,
+                achieved,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn check_http_maps_each_outcome() {
+        let hash = "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e";
+        assert_eq!(
+            HashFinder::new(4).check_http(hash),
+            CheckHttp::Accepted { difficulty: 5 }
+        );
+
+        assert_eq!(
+            HashFinder::new(6).check_http(hash),
+            CheckHttp::InsufficientWork {
+                required: 6,
+                achieved: 5
+            }
+        );
+
+        let hash = "3c+727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+        assert_eq!(
+            HashFinder::new(4).check_http(hash),
+            CheckHttp::Malformed {
+                reason: String::from("Invalid character '+' at position 2")
+            }
+        )
+    }
+
+    #[test]
+    fn required_digits_round_up_the_bits() {
+        // The target hash is 000d4cbf…, with 12 leading zero bits
+        let hash = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+
+        assert_eq!(
+            HashFinder::with_bits(13).check_http(hash),
+            CheckHttp::InsufficientWork {
+                required: 4,
+                achieved: 3
+            }
+        );
+        assert_eq!(
+            HashFinder::with_bits(12).check_http(hash),
+            CheckHttp::Accepted { difficulty: 3 }
+        )
+    }
+
+    #[test]
+    fn achieved_difficulty_uses_the_settings_of_the_check() {
+        let hash_finder = HashFinder::new(2).with_rounds(4).with_domain(b"http");
+        let origin_hash = hash_finder.find();
+
+        assert_eq!(
+            hash_finder.check_http(&hex::encode(origin_hash)),
+            CheckHttp::Accepted {
+                difficulty: hash_finder.check_difficulty(&origin_hash) as u8
+            }
+        )
+    }
+
+    #[test]
+    fn status_codes_follow_http_semantics() {
+        let accepted = CheckHttp::Accepted { difficulty: 5 };
+        let insufficient = CheckHttp::InsufficientWork {
+            required: 6,
+            achieved: 5,
+        };
+        let malformed = CheckHttp::Malformed {
+            reason: String::new(),
+        };
+
+        assert_eq!(accepted.status_code(), 200);
+        assert_eq!(insufficient.status_code(), 403);
+        assert_eq!(malformed.status_code(), 400)
+    }
+}
@@ -0,0 +1,245 @@
+//! Proofs whose second round is a keyed Blake2s MAC, so that only holders of the key can find or check them.
+//!
+//! The target hash of a [`HashFinder`] only depends on the origin hash, so anybody can check a proof, including a
+//! third party pre-validating solutions to resell them. With [`KeyedFinder`] the target hash is the Blake2s MAC of
+//! the origin hash under a 32-byte key. The server either hands its key to the clients it trusts or derives a key per
+//! challenge with [`KeyedFinder::for_challenge`] and sends it along with the challenge, keeping its secret to itself.
+
+use core::fmt;
+
+use blake2::digest::{KeyInit, Mac};
+use blake2::{Blake2s256, Blake2sMac256};
+
+use crate::{
+    decode_origin_hash, Entropy, Finder, HashFinder, PowError, SaltCounter, DEFAULT_ROUNDS,
+    DEFAULT_VERSION,
+};
+
+/// Prefix of the MAC that derives the key of a challenge from the secret of the server.
+const CHALLENGE_KEY_PREFIX: &[u8] = b"pow_account/keyed/";
+
+/// `KeyedFinder` finds and checks origin hashes like [`HashFinder`], with a keyed Blake2s MAC as the second round.
+///
+/// Origin hashes are drawn as with [`HashFinder::find`]. The second round is the Blake2s MAC under the key of the
+/// version, the domain tag and the origin hash, and any further rounds are plain Blake2s hashes, so the difficulty,
+/// version, rounds and domain of the HashFinder keep their meaning. The digest of the HashFinder is not used. An
+/// origin hash found by one KeyedFinder only passes the checks of a KeyedFinder with the same key and settings.
+///
+/// The key is left out of the [`Debug`](fmt::Debug) output so that it doesn't end up in logs.
+///
+/// # Example
+/// ```
+/// use pow_account::{HashFinder, KeyedFinder};
+///
+/// let secret = [7u8; 32];
+/// let finder = KeyedFinder::for_challenge(HashFinder::new(3), &secret, b"challenge-42");
+/// let origin_hash = finder.find();
+///
+/// assert!(finder.check(hex::encode(origin_hash)).unwrap());
+///
+/// let other = KeyedFinder::for_challenge(HashFinder::new(3), &secret, b"challenge-43");
+/// assert_ne!(finder.target_hash(&origin_hash), other.target_hash(&origin_hash));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct KeyedFinder {
+    hash_finder: HashFinder,
+    key: [u8; 32],
+}
+
+impl KeyedFinder {
+    /// Returns a KeyedFinder with the settings of `hash_finder` and the MAC key `key`
+    pub fn new(hash_finder: HashFinder, key: [u8; 32]) -> Self {
+        KeyedFinder { hash_finder, key }
+    }
+
+    /// Returns a KeyedFinder whose key is derived from the secret of the server and a challenge
+    ///
+    /// The key is the Blake2s MAC of the challenge under `secret`. The server sends it to the client with the
+    /// challenge, which lets the client find a proof for that challenge without learning the secret or being able to
+    /// check proofs of any other challenge.
+    ///
+    /// # Parameters
+    ///
+    /// - `hash_finder`: The difficulty and settings of the proofs.
+    /// - `secret`: The secret of the server, which never leaves it.
+    /// - `challenge`: The bytes identifying the challenge, such as its nonce.
+    pub fn for_challenge(hash_finder: HashFinder, secret: &[u8; 32], challenge: &[u8]) -> Self {
+        let mut mac = <Blake2sMac256 as KeyInit>::new(secret.into());
+        mac.update(CHALLENGE_KEY_PREFIX);
+        mac.update(challenge);
+        KeyedFinder::new(hash_finder, mac.finalize().into_bytes().into())
+    }
+
+    /// Returns the HashFinder whose settings the proofs follow
+    pub fn hash_finder(&self) -> &HashFinder {
+        &self.hash_finder
+    }
+
+    /// Returns the MAC key, for instance to send it to the client along with its challenge
+    pub fn key(&self) -> [u8; 32] {
+        self.key
+    }
+
+    /// Finds an origin hash whose keyed target hash is below the target
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    pub fn find(&self) -> [u8; 32] {
+        let mut counter = SaltCounter::reserve();
+        loop {
+            let origin_hash = self.hash_finder.origin_candidate(&mut counter);
+            if self.check_bytes(&origin_hash) {
+                return origin_hash;
+            }
+        }
+    }
+
+    /// Finds an origin hash, giving up after `max_attempts` attempts
+    ///
+    /// This bounds the time spent on the calling thread, as [`HashFinder::find_with_limit`] does.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Some` origin hash, or `None` if none was found within `max_attempts` attempts.
+    pub fn find_with_limit(&self, max_attempts: u64) -> Option<[u8; 32]> {
+        let mut counter = SaltCounter::reserve();
+        (0..max_attempts)
+            .map(|_| self.hash_finder.origin_candidate(&mut counter))
+            .find(|origin_hash| self.check_bytes(origin_hash))
+    }
+
+    /// Determines whether a given origin hash produces a keyed target hash below the target
+    ///
+    /// # Parameters
+    ///
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    pub fn check<S: AsRef<[u8]>>(&self, origin_hash: S) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+
+        Ok(self.check_bytes(&origin_hash_bytes))
+    }
+
+    /// Determines whether a decoded origin hash produces a keyed target hash below the target
+    pub fn check_bytes(&self, origin_hash: &[u8; 32]) -> bool {
+        self.hash_finder
+            .meets_target(&self.target_hash(origin_hash))
+    }
+
+    /// Returns the keyed target hash of an origin hash, which is compared to the target
+    pub fn target_hash(&self, origin_hash: &[u8; 32]) -> [u8; 32] {
+        let mut mac = <Blake2sMac256 as KeyInit>::new(&self.key.into());
+        if self.hash_finder.version != DEFAULT_VERSION {
+            mac.update(&[self.hash_finder.version]);
+        }
+        if let Some(domain_tag) = &self.hash_finder.domain {
+            mac.update(domain_tag);
+        }
+        mac.update(origin_hash);
+        let target_hash = mac.finalize().into_bytes().into();
+
+        (DEFAULT_ROUNDS..self.hash_finder.rounds).fold(target_hash, |target_hash, _| {
+            Entropy::from(target_hash).hash::<Blake2s256>()
+        })
+    }
+}
+
+impl fmt::Debug for KeyedFinder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedFinder")
+            .field("hash_finder", &self.hash_finder)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The proof is the origin hash of [`KeyedFinder::find`], verified with [`KeyedFinder::check_bytes`].
+impl Finder for KeyedFinder {
+    type Proof = [u8; 32];
+
+    fn find_proof(&self) -> Self::Proof {
+        self.find()
+    }
+
+    fn verify_proof(&self, proof: &Self::Proof) -> bool {
+        self.check_bytes(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn found_hash_passes_the_check() {
+        for hash_finder in [
+            HashFinder::new(3),
+            HashFinder::with_version(2, 2).with_rounds(4),
+            HashFinder::new(2).with_domain(b"service-a"),
+        ] {
+            let finder = KeyedFinder::new(hash_finder, [1; 32]);
+            let origin_hash = finder.find();
+
+            assert!(finder.check_bytes(&origin_hash), "{hash_finder:?}");
+            assert!(finder.verify_proof(&origin_hash));
+            assert!(finder.check(hex::encode(origin_hash)).unwrap())
+        }
+    }
+
+    #[test]
+    fn proofs_depend_on_the_key() {
+        let finder = KeyedFinder::new(HashFinder::with_bits(16), [1; 32]);
+        let other = KeyedFinder::new(HashFinder::with_bits(16), [2; 32]);
+        let origin_hash = finder.find();
+
+        assert!(!other.check_bytes(&origin_hash));
+        assert_ne!(
+            finder.target_hash(&origin_hash),
+            Entropy::from(origin_hash).hash::<Blake2s256>()
+        )
+    }
+
+    #[test]
+    fn settings_change_the_keyed_target_hash() {
+        let origin_hash = [0x5a; 32];
+        let target_hash =
+            |hash_finder| KeyedFinder::new(hash_finder, [1; 32]).target_hash(&origin_hash);
+
+        let plain = target_hash(HashFinder::new(3));
+        assert_ne!(plain, target_hash(HashFinder::with_version(3, 2)));
+        assert_ne!(
+            plain,
+            target_hash(HashFinder::new(3).with_domain(b"service-a"))
+        );
+        assert_eq!(
+            target_hash(HashFinder::new(3).with_rounds(3)),
+            Entropy::from(plain).hash::<Blake2s256>()
+        );
+        assert_eq!(plain, target_hash(HashFinder::new(5)))
+    }
+
+    #[test]
+    fn challenge_keys_are_derived_from_the_secret() {
+        let hash_finder = HashFinder::new(3);
+        let key = |secret: &[u8; 32], challenge: &[u8]| {
+            KeyedFinder::for_challenge(hash_finder, secret, challenge).key()
+        };
+
+        assert_eq!(key(&[7; 32], b"a"), key(&[7; 32], b"a"));
+        assert_ne!(key(&[7; 32], b"a"), key(&[7; 32], b"b"));
+        assert_ne!(key(&[7; 32], b"a"), key(&[8; 32], b"a"));
+        assert_ne!(key(&[7; 32], b"a"), [7; 32])
+    }
+
+    #[test]
+    fn key_is_left_out_of_the_debug_output() {
+        let debug = format!("{:?}", KeyedFinder::new(HashFinder::new(3), [0xab; 32]));
+        assert!(debug.starts_with("KeyedFinder { hash_finder: "));
+        assert!(!debug.contains("171"))
+    }
+}
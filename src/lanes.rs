@@ -0,0 +1,282 @@
+//! Multi-lane Blake2s search, enabled with the `simd` feature.
+//!
+//! Every hash of the search has a 32-byte input, a single Blake2s block. On x86_64 CPUs with AVX2, selected at runtime,
+//! [`hash_lanes`] compresses [`LANES`] such blocks at once with every state word held in a 256-bit register of one
+//! word per lane, about four times the throughput of hashing them one by one. Other CPUs hash the lanes one by one
+//! with the `blake2` crate, so the results are identical everywhere.
+
+use blake2::{Blake2s256, Digest};
+use rand_core::{OsRng, RngCore};
+
+use crate::{Entropy, HashFinder, DEFAULT_ROUNDS, DEFAULT_VERSION};
+
+/// Number of candidates hashed together.
+pub const LANES: usize = 8;
+
+/// Hashes [`LANES`] 32-byte inputs with Blake2s, as `Blake2s256::digest` would one by one
+///
+/// # Example
+/// ```
+/// use blake2::{Blake2s256, Digest};
+/// use pow_account::{hash_lanes, LANES};
+///
+/// let inputs: [[u8; 32]; LANES] = core::array::from_fn(|lane| [lane as u8; 32]);
+/// let hashes = hash_lanes(&inputs);
+///
+/// assert_eq!(hashes[3], <[u8; 32]>::from(Blake2s256::digest([3u8; 32])));
+/// ```
+pub fn hash_lanes(inputs: &[[u8; 32]; LANES]) -> [[u8; 32]; LANES] {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2, the only requirement of the function
+        return unsafe { avx2::hash_lanes(inputs) };
+    }
+
+    hash_lanes_portable(inputs)
+}
+
+fn hash_lanes_portable(inputs: &[[u8; 32]; LANES]) -> [[u8; 32]; LANES] {
+    inputs.map(|input| Blake2s256::digest(input).into())
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use core::arch::x86_64::{
+        __m256i, _mm256_add_epi32, _mm256_loadu_si256, _mm256_or_si256, _mm256_set1_epi32,
+        _mm256_setr_epi8, _mm256_shuffle_epi8, _mm256_slli_epi32, _mm256_srli_epi32,
+        _mm256_storeu_si256, _mm256_xor_si256,
+    };
+
+    use super::LANES;
+
+    const IV: [u32; 8] = [
+        0x6A09_E667,
+        0xBB67_AE85,
+        0x3C6E_F372,
+        0xA54F_F53A,
+        0x510E_527F,
+        0x9B05_688C,
+        0x1F83_D9AB,
+        0x5BE0_CD19,
+    ];
+
+    const SIGMA: [[usize; 16]; 10] = [
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+        [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+        [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+        [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+        [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+        [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+        [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+        [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+        [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+        [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    ];
+
+    /// Parameter block of an unkeyed Blake2s with a 32-byte digest, XORed into the first word of the IV.
+    const PARAMETERS: u32 = 0x0101_0020;
+
+    /// Compresses the single block of every lane, the 32 input bytes followed by 32 zero bytes.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn hash_lanes(inputs: &[[u8; 32]; LANES]) -> [[u8; 32]; LANES] {
+        let mut words = [[0u32; LANES]; 16];
+        for (lane, input) in inputs.iter().enumerate() {
+            for (word, bytes) in input.chunks_exact(4).enumerate() {
+                words[word][lane] = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            }
+        }
+        // SAFETY: every row holds the 8 words of a 256-bit load
+        let m: [__m256i; 16] =
+            core::array::from_fn(|word| unsafe { _mm256_loadu_si256(words[word].as_ptr().cast()) });
+
+        let mut h = IV;
+        h[0] ^= PARAMETERS;
+        let splat = |word: u32| _mm256_set1_epi32(word as i32);
+
+        // The counter is the 32 bytes of input and the block is the last one
+        let mut v = [
+            splat(h[0]),
+            splat(h[1]),
+            splat(h[2]),
+            splat(h[3]),
+            splat(h[4]),
+            splat(h[5]),
+            splat(h[6]),
+            splat(h[7]),
+            splat(IV[0]),
+            splat(IV[1]),
+            splat(IV[2]),
+            splat(IV[3]),
+            splat(IV[4] ^ 32),
+            splat(IV[5]),
+            splat(!IV[6]),
+            splat(IV[7]),
+        ];
+
+        for s in &SIGMA {
+            g(&mut v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+            g(&mut v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+            g(&mut v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+            g(&mut v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+            g(&mut v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+            g(&mut v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+            g(&mut v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+            g(&mut v, [3, 4, 9, 14], m[s[14]], m[s[15]]);
+        }
+
+        for (word, h) in h.iter().enumerate() {
+            let output = _mm256_xor_si256(_mm256_xor_si256(splat(*h), v[word]), v[word + 8]);
+            // SAFETY: every row has room for the 8 words of a 256-bit store
+            unsafe { _mm256_storeu_si256(words[word].as_mut_ptr().cast(), output) };
+        }
+
+        let mut hashes = [[0u8; 32]; LANES];
+        for (word, words) in words[..8].iter().enumerate() {
+            for (hash, value) in hashes.iter_mut().zip(words) {
+                hash[4 * word..4 * word + 4].copy_from_slice(&value.to_le_bytes());
+            }
+        }
+        hashes
+    }
+
+    /// Mixes the four words at `[a, b, c, d]` with two message words, in every lane.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn g(v: &mut [__m256i; 16], [a, b, c, d]: [usize; 4], x: __m256i, y: __m256i) {
+        v[a] = _mm256_add_epi32(_mm256_add_epi32(v[a], v[b]), x);
+        v[d] = rotate_right_16(_mm256_xor_si256(v[d], v[a]));
+        v[c] = _mm256_add_epi32(v[c], v[d]);
+        v[b] = rotate_right::<12, 20>(_mm256_xor_si256(v[b], v[c]));
+        v[a] = _mm256_add_epi32(_mm256_add_epi32(v[a], v[b]), y);
+        v[d] = rotate_right_8(_mm256_xor_si256(v[d], v[a]));
+        v[c] = _mm256_add_epi32(v[c], v[d]);
+        v[b] = rotate_right::<7, 25>(_mm256_xor_si256(v[b], v[c]));
+    }
+
+    /// Rotates every word right by `R` bits, where `L` is `32 - R`.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn rotate_right<const R: i32, const L: i32>(x: __m256i) -> __m256i {
+        _mm256_or_si256(_mm256_srli_epi32::<R>(x), _mm256_slli_epi32::<L>(x))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn rotate_right_16(x: __m256i) -> __m256i {
+        _mm256_shuffle_epi8(
+            x,
+            _mm256_setr_epi8(
+                2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 2, 3, 0, 1, 6, 7, 4, 5, 10,
+                11, 8, 9, 14, 15, 12, 13,
+            ),
+        )
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn rotate_right_8(x: __m256i) -> __m256i {
+        _mm256_shuffle_epi8(
+            x,
+            _mm256_setr_epi8(
+                1, 2, 3, 0, 5, 6, 7, 4, 9, 10, 11, 8, 13, 14, 15, 12, 1, 2, 3, 0, 5, 6, 7, 4, 9,
+                10, 11, 8, 13, 14, 15, 12,
+            ),
+        )
+    }
+}
+
+impl HashFinder<Blake2s256> {
+    /// Finds an origin hash by hashing [`LANES`] candidates at a time
+    ///
+    /// As with [`HashFinder::find_counter`], 32 random bytes are drawn once and every candidate is derived from them
+    /// and a counter. The candidate is used as the origin hash without hashing it first, which saves a hash per
+    /// attempt, and the target hashes of [`LANES`] candidates are computed together with [`hash_lanes`]. A HashFinder
+    /// with a version or a domain tag hashes longer inputs, which are hashed one lane at a time. The origin hashes are
+    /// checked exactly like the ones found by [`HashFinder::find`]; the `search` benchmark compares their throughput.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::new(3).find_lanes();
+    /// assert!(HashFinder::new(3).check(hex::encode(origin_hash)).unwrap());
+    /// ```
+    pub fn find_lanes(&self) -> [u8; 32] {
+        let mut base = [0u8; 32];
+        OsRng.fill_bytes(&mut base);
+
+        (0..=u64::MAX / LANES as u64)
+            .find_map(|batch| self.lanes_attempt(base, batch * LANES as u64))
+            .expect("no origin hash found in 2^64 attempts")
+    }
+
+    fn lanes_attempt(&self, base: [u8; 32], first_index: u64) -> Option<[u8; 32]> {
+        let origin_hashes: [[u8; 32]; LANES] = core::array::from_fn(|lane| {
+            Entropy::from_counter(base, first_index + lane as u64).entropy
+        });
+
+        let target_hashes = match self.version == DEFAULT_VERSION && self.domain.is_none() {
+            true => (DEFAULT_ROUNDS..self.rounds)
+                .fold(hash_lanes(&origin_hashes), |target_hashes, _| {
+                    hash_lanes(&target_hashes)
+                }),
+            false => origin_hashes.map(|origin_hash| self.target_hash(origin_hash)),
+        };
+
+        origin_hashes
+            .into_iter()
+            .zip(target_hashes)
+            // The comparison doesn't need to be constant time in a search, and costs more than the hash when it is
+            .find(|(_, target_hash)| *target_hash < self.target)
+            .map(|(origin_hash, _)| origin_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn lanes_match_blake2s() {
+        let mut inputs = [[0u8; 32]; LANES];
+        for input in inputs.iter_mut() {
+            OsRng.fill_bytes(input);
+        }
+
+        let expected = inputs.map(|input| <[u8; 32]>::from(Blake2s256::digest(input)));
+        assert_eq!(hash_lanes(&inputs), expected);
+        assert_eq!(hash_lanes_portable(&inputs), expected)
+    }
+
+    #[test]
+    fn lanes_match_the_blake2s_test_vector() {
+        // Blake2s-256 of 32 zero bytes
+        let hashes = hash_lanes(&[[0u8; 32]; LANES]);
+        assert_eq!(
+            hex::encode(hashes[LANES - 1]),
+            "320b5ea99e653bc2b593db4130d10a4efd3a0b4cc2e1a6672b678d71dfbd33ad"
+        )
+    }
+
+    #[test]
+    fn found_hash_passes_the_check() {
+        for hash_finder in [
+            HashFinder::new(3),
+            HashFinder::new(2).with_rounds(3),
+            HashFinder::new(2).with_domain(b"lanes"),
+        ] {
+            let origin_hash = hash_finder.find_lanes();
+            assert!(hash_finder.check_bytes(&origin_hash), "{hash_finder:?}")
+        }
+    }
+}
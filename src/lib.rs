@@ -20,18 +20,211 @@
 //!
 //! ## Additional Information
 //! For more details, refer to the [README](https://github.com/1prefix/pow-account/blob/main/README.md).
+//!
+//! ## Features
+//! - `std` (enabled by default): the searches using the operating system random number generator, hexadecimal
+//!   input and output, and every API that needs the standard library. Without it the crate is `no_std` and keeps
+//!   [`HashFinder::find_with_rng`], [`HashFinder::check_bytes`] and the other checks of decoded origin hashes,
+//!   none of which allocates.
+//! - `parallel`: [`HashFinder::check_many`] checks its batch on one thread per available core. It requires `std`.
+//! - `serde`: `Serialize` and `Deserialize` implementations for [`HashFinder`], [`Challenge`] and [`Solution`],
+//!   with hexadecimal byte arrays in human-readable formats. It requires `std`.
+//! - `tokio`: [`HashFinder::find_async`] runs a search on the Tokio blocking thread pool. It requires `std`.
+//! - `wasm`: draws the operating system randomness from the JavaScript `crypto.getRandomValues` API, so the crate
+//!   runs in browsers when compiled to `wasm32-unknown-unknown`. Threads and [`Instant`] are not
+//!   available there, so [`HashFinder::find_with_limit`] is the supported way to search. The [`wasm`] module
+//!   exports `find`, `check` and a `findAsync` that doesn't freeze the page to JavaScript. It requires `std`.
+//! - `tower`: [`PowLayer`], a `tower` layer that rejects requests without a valid proof of work in their headers,
+//!   for a challenge signed by a [`PowGate`], as described in the [`middleware`] module. It requires `std`.
+//! - `axum`: [`PowSolution`], an `axum` extractor with the same verification. It requires `std`.
+//! - `redis`: [`RedisSolutionStore`], a [`SolutionStore`] shared by several instances through Redis. It requires
+//!   `std`.
+//! - `hashcash`: the [`hashcash`] module, which mints and checks Hashcash version 1 stamps. It requires `std`.
+//! - `argon2`: [`MemoryHardFinder`], whose second round is Argon2id, so every attempt needs a configurable amount of
+//!   memory. It requires `std`.
+//! - `simd`: [`HashFinder::find_lanes`], a search that hashes [`LANES`] candidates at a time with AVX2 when the CPU
+//!   supports it, detected at runtime. It requires `std`.
+//! - `gpu`: [`GpuFinder`], which runs the search as a `wgpu` compute shader and on the CPU when no adapter is
+//!   available. It requires `std`.
+//! - `cli`: the `pow-account` binary with `find`, `check` and `bench` commands. It requires `serde`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+mod benchmark;
+#[cfg(feature = "std")]
+mod bucket;
+mod builder;
+mod certificate;
+mod challenge;
+mod checked;
+#[cfg(feature = "std")]
+mod collision;
+mod compact;
+#[cfg(feature = "std")]
+mod context;
+#[cfg(feature = "std")]
+mod controller;
+#[cfg(feature = "std")]
+mod covering;
+mod decimal;
+mod difficulty;
+mod error;
+#[cfg(feature = "std")]
+mod finder;
+#[cfg(feature = "gpu")]
+mod gpu;
+#[cfg(feature = "hashcash")]
+pub mod hashcash;
+#[cfg(feature = "std")]
+mod http;
+#[cfg(feature = "std")]
+mod keyed;
+#[cfg(feature = "simd")]
+mod lanes;
+#[cfg(feature = "argon2")]
+mod memory_hard;
+#[cfg(any(feature = "tower", feature = "axum"))]
+pub mod middleware;
+#[cfg(feature = "std")]
+mod parallel;
+#[cfg(feature = "std")]
+mod policy;
+#[cfg(feature = "redis")]
+mod redis_store;
+#[cfg(feature = "std")]
+mod self_test;
+#[cfg(feature = "serde")]
+mod serialization;
+mod signed;
+#[cfg(feature = "std")]
+mod solution;
+#[cfg(feature = "std")]
+mod store;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "tokio")]
+mod task;
+pub mod test_vectors;
+#[cfg(feature = "std")]
+mod token;
+mod verifier;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "std")]
+pub use benchmark::{benchmark_hashrate, benchmark_hashrate_ci, Calibrator};
+#[cfg(feature = "std")]
+pub use bucket::WorkBucket;
+pub use builder::HashFinderBuilder;
+pub use certificate::Certificate;
+pub use challenge::Challenge;
+pub use checked::{encode_checked, CHECKED_LEN};
+#[cfg(feature = "std")]
+pub use collision::{CollisionFinder, CollisionProof, MAX_COLLISION_BITS};
+#[cfg(feature = "std")]
+pub use controller::{DifficultyController, DEFAULT_SMOOTHING};
+pub use decimal::DECIMAL_DIGITS;
+pub use difficulty::MAX_DIFFICULTY_BITS;
+pub use error::{Error, PowError};
+#[cfg(feature = "std")]
+pub use finder::Finder;
+#[cfg(feature = "gpu")]
+pub use gpu::{GpuFinder, GPU_BATCH};
+#[cfg(feature = "std")]
+pub use http::CheckHttp;
+#[cfg(feature = "std")]
+pub use keyed::KeyedFinder;
+#[cfg(feature = "simd")]
+pub use lanes::{hash_lanes, LANES};
+#[cfg(feature = "argon2")]
+pub use memory_hard::{MemoryHardFinder, DEFAULT_ITERATIONS, DEFAULT_MEMORY_COST};
+#[cfg(any(feature = "tower", feature = "axum"))]
+pub use middleware::PowGate;
+#[cfg(feature = "tower")]
+pub use middleware::{PowLayer, PowService};
+#[cfg(feature = "axum")]
+pub use middleware::{PowRejection, PowSolution};
+#[cfg(feature = "std")]
+pub use policy::{
+    PolicyEngine, DEFAULT_BITS_PER_LEVEL, DEFAULT_REQUESTS_PER_LEVEL, DEFAULT_WINDOW,
+};
+#[cfg(feature = "redis")]
+pub use redis_store::{RedisSolutionStore, DEFAULT_KEY_PREFIX, DEFAULT_STORE_TIMEOUT};
+#[cfg(feature = "std")]
+pub use self_test::self_test;
+pub use signed::ChallengeKey;
+#[cfg(feature = "std")]
+pub use solution::{Progress, Solution};
+#[cfg(feature = "std")]
+pub use store::{MemorySolutionStore, SolutionStore};
+#[cfg(feature = "std")]
+pub use token::PowToken;
+pub use verifier::Verifier;
 
+use core::fmt;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::sync::mpsc::SyncSender;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use blake2::digest::consts::U32;
 use blake2::{Blake2s256, Digest};
-use rand_core::{OsRng, RngCore};
+#[cfg(feature = "std")]
+use rand_core::OsRng;
+use rand_core::RngCore;
+use subtle::{Choice, ConstantTimeEq, ConstantTimeLess};
+
+/// Process-wide counter handing out the starting values of the [`SaltCounter`] of every search.
+#[cfg(feature = "std")]
+static SEARCH_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Counter appended to the entropy of every attempt of a single search when the counter salt is enabled.
+///
+/// A search reserves its starting value once and then increments its own copy, so searches running on several
+/// threads never contend on a shared atomic.
+struct SaltCounter {
+    next: u64,
+}
+
+impl SaltCounter {
+    /// Returns a counter whose high 32 bits are the next value of the process-wide counter, so that the attempts of
+    /// concurrent searches hash distinct inputs for their first 2^32 attempts.
+    #[cfg(feature = "std")]
+    fn reserve() -> Self {
+        SaltCounter {
+            next: SEARCH_COUNTER.fetch_add(1, Ordering::Relaxed) << 32,
+        }
+    }
+
+    /// Returns a counter starting at 0, for searches that have to be reproducible.
+    fn from_zero() -> Self {
+        SaltCounter { next: 0 }
+    }
+
+    fn next(&mut self) -> u64 {
+        let value = self.next;
+        self.next = value.wrapping_add(1);
+        value
+    }
+}
 
 struct Entropy {
     entropy: [u8; 32],
 }
 
 impl Entropy {
+    #[cfg(feature = "std")]
     fn new() -> Self {
+        Entropy::from_rng(&mut OsRng)
+    }
+
+    fn from_rng<R: RngCore>(rng: &mut R) -> Self {
         let mut entropy = [0u8; 32];
-        OsRng::default().fill_bytes(&mut entropy);
+        rng.fill_bytes(&mut entropy);
 
         Entropy { entropy }
     }
@@ -40,71 +233,384 @@ impl Entropy {
         Entropy { entropy }
     }
 
-    fn hash(&self) -> [u8; 32] {
-        let mut hash = Blake2s256::new();
-        let _ = hash.update(self.entropy);
+    fn from_counter(base: [u8; 32], counter: u64) -> Self {
+        let mut entropy = base;
+        for (byte, counter_byte) in entropy[24..].iter_mut().zip(counter.to_be_bytes()) {
+            *byte ^= counter_byte;
+        }
+
+        Entropy { entropy }
+    }
+
+    fn hash<D: Digest<OutputSize = U32>>(&self) -> [u8; 32] {
+        let mut hash = D::new();
+        hash.update(self.entropy);
+        hash.finalize().into()
+    }
+
+    fn hash_with_suffix<D: Digest<OutputSize = U32>>(&self, suffix: &[u8]) -> [u8; 32] {
+        let mut hash = D::new();
+        hash.update(self.entropy);
+        hash.update(suffix);
+        hash.finalize().into()
+    }
+
+    fn hash_with_prefix<D: Digest<OutputSize = U32>>(&self, prefix: &[&[u8]]) -> [u8; 32] {
+        let mut hash = D::new();
+        for part in prefix {
+            hash.update(part);
+        }
+        hash.update(self.entropy);
         hash.finalize().into()
     }
 }
 
+/// Prefix of the hash that turns an application domain into the 32 bytes mixed into the target hash.
+const DOMAIN_TAG_PREFIX: &[u8] = b"pow_account/domain/";
+
+fn domain_tag<D: Digest<OutputSize = U32>>(domain: &[u8]) -> [u8; 32] {
+    let mut hash = D::new();
+    hash.update(DOMAIN_TAG_PREFIX);
+    hash.update(domain);
+    hash.finalize().into()
+}
+
+/// What the 32 bytes mixed into a target hash before the origin hash bind a proof to.
+///
+/// The kind of binding is hashed along with its bytes, so that a proof bound to a challenge never passes the check of
+/// a pre-hashed payload, a context or a batch of records made of the same bytes, nor the check of a domain.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Binding {
+    Challenge = 1,
+    #[cfg(feature = "std")]
+    Prehash = 2,
+    #[cfg(feature = "std")]
+    Context = 3,
+    #[cfg(feature = "std")]
+    Records = 4,
+}
+
+fn decode_origin_hash<T: AsRef<[u8]>>(origin_hash: T) -> Result<[u8; 32], PowError> {
+    let origin_hash = origin_hash.as_ref();
+    if origin_hash.len() != 64 {
+        return Err(PowError::InvalidLength {
+            len: origin_hash.len(),
+            expected: 64,
+        });
+    }
+
+    let mut origin_hash_bytes: [u8; 32] = [0u8; 32];
+    hex::decode_to_slice(origin_hash, &mut origin_hash_bytes)?;
+    Ok(origin_hash_bytes)
+}
+
+/// Computes the Blake2s digest of a payload for [`HashFinder::find_with_prehash`]
+///
+/// # Example
+///
+/// ```rust
+/// use pow_account::payload_prehash;
+///
+/// let payload_hash = payload_prehash(b"account-request");
+/// assert_eq!(payload_hash.len(), 32);
+/// ```
+pub fn payload_prehash(payload: &[u8]) -> [u8; 32] {
+    let mut hash = Blake2s256::new();
+    hash.update(payload);
+    hash.finalize().into()
+}
+
+/// Returns the amount of work demonstrated by an origin hash
+///
+/// The work is `2^n`, where `n` is the number of leading zero bits of the target hash. It is the expected number
+/// of attempts needed to find an origin hash at least as good as this one.
+///
+/// # Example
+///
+/// ```rust
+/// use pow_account::{work_of, HashFinder};
+///
+/// let origin_hash = HashFinder::new(3).find();
+/// assert!(work_of(&origin_hash) >= 4096.0);
+/// ```
+#[cfg(feature = "std")]
+pub fn work_of(origin_hash: &[u8; 32]) -> f64 {
+    let target_hash = Entropy::from(*origin_hash).hash::<Blake2s256>();
+    2f64.powi(leading_zero_bits(&target_hash) as i32)
+}
+
+/// Determines whether two origin hashes produce the same target hash
+///
+/// The target hashes are computed with the default hashing construction. A genuine collision between two distinct
+/// origin hashes is practically impossible, so a `true` result for distinct inputs points to a replayed or derived
+/// submission rather than to independent work.
+///
+/// # Example
+///
+/// ```rust
+/// use pow_account::{same_target, HashFinder};
+///
+/// let origin_hash = HashFinder::new(2).find();
+/// assert!(same_target(&origin_hash, &origin_hash));
+/// ```
+pub fn same_target(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    Entropy::from(*a).hash::<Blake2s256>() == Entropy::from(*b).hash::<Blake2s256>()
+}
+
+/// Determines whether an origin hash meets a difficulty of `required_bits` leading zero bits
+///
+/// This is a shortcut for `HashFinder::with_bits(required_bits).check_bytes(origin_hash)` for proofs that carry
+/// their own difficulty, such as a [`PowToken`]. The target hash is computed with the default hashing construction.
+///
+/// # Example
+///
+/// ```rust
+/// use pow_account::{verify, HashFinder};
+///
+/// let origin_hash = HashFinder::with_bits(10).find();
+/// assert!(verify(&origin_hash, 10));
+/// ```
+pub fn verify(origin_hash: &[u8; 32], required_bits: u32) -> bool {
+    HashFinder::with_bits(required_bits).check_bytes(origin_hash)
+}
+
+fn leading_zero_digits(origin_hash: &[u8; 32]) -> u32 {
+    leading_zero_bits(&Entropy::from(*origin_hash).hash::<Blake2s256>()) / 4
+}
+
+/// Determines whether `hash < target` as big-endian 256-bit values in constant time.
+///
+/// Every byte is compared whatever the outcome, so the time taken doesn't reveal how close a submitted hash came
+/// to the target.
+fn ct_lt(hash: &[u8; 32], target: &[u8; 32]) -> bool {
+    let mut lower = Choice::from(0);
+    let mut equal = Choice::from(1);
+
+    for (hash_byte, target_byte) in hash.iter().zip(target) {
+        lower |= equal & hash_byte.ct_lt(target_byte);
+        equal &= hash_byte.ct_eq(target_byte);
+    }
+    lower.into()
+}
+
+/// Returns the number of leading zeros of a target hash, as reported by [`HashFinder::check_difficulty`].
+fn target_difficulty(target_hash: &[u8; 32]) -> u32 {
+    leading_zero_bits(target_hash) / 4
+}
+
+fn leading_zero_bits(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
 struct HashPrefix {
-    zero_bits: u8,
+    zero_bits: u32,
+    target: [u8; 32],
 }
 
 impl Default for HashPrefix {
     fn default() -> Self {
-        HashPrefix { zero_bits: 20u8 }
+        HashPrefix::from_bits(20)
     }
 }
 
 impl HashPrefix {
     fn new(leading_zeros: u8) -> Self {
-        HashPrefix {
-            zero_bits: 8 / 2 * leading_zeros,
-        }
+        HashPrefix::from_bits(4 * u32::from(leading_zeros))
+    }
+
+    fn from_bits(zero_bits: u32) -> Self {
+        let mut prefix = HashPrefix {
+            zero_bits: 0,
+            target: [255u8; 32],
+        };
+        prefix.set_zero_bits(zero_bits);
+        prefix.recompute_target();
+        prefix
     }
 }
 
 impl HashPrefix {
-    fn get(&self) -> u8 {
+    fn get(&self) -> u32 {
         self.zero_bits
     }
 
-    fn target(&self) -> [u8; 32] {
-        let target: [u8; 16] = self.target_u128().to_be_bytes();
-        let mut array: [u8; 32] = [255u8; 32];
-        array[0..16].copy_from_slice(target.as_slice());
-        array
+    /// Changes the number of zero bits, capped at [`MAX_DIFFICULTY_BITS`], without touching the target until
+    /// [`HashPrefix::recompute_target`] is called
+    fn set_zero_bits(&mut self, bits: u32) {
+        self.zero_bits = bits.min(MAX_DIFFICULTY_BITS);
     }
 
-    fn target_u128(&self) -> u128 {
-        let total_bits = 128;
-        let remaining_bits = total_bits - self.get();
+    /// Sets the target to the 256-bit value whose `zero_bits` most significant bits are zero and all other bits are one
+    fn recompute_target(&mut self) {
+        let mut array: [u8; 32] = [255u8; 32];
+        let mut remaining_bits = self.get();
 
-        match self.get() < total_bits {
-            true => (1u128 << remaining_bits) - 1,
-            false => 1,
+        for byte in array.iter_mut() {
+            match remaining_bits {
+                0 => break,
+                1..=7 => {
+                    *byte = 255u8 >> remaining_bits;
+                    remaining_bits = 0;
+                }
+                _ => {
+                    *byte = 0;
+                    remaining_bits -= 8;
+                }
+            }
         }
+
+        self.target = array;
+    }
+
+    fn target(&self) -> [u8; 32] {
+        self.target
     }
 }
 
+/// Number of attempts between two reads of the clock or of a cancellation flag during a search.
+#[cfg(feature = "std")]
+const POLL_INTERVAL: u64 = 2048;
+
+/// Number of attempts between two calls of the callback of [`HashFinder::find_with_progress`].
+pub const PROGRESS_INTERVAL: u64 = 100_000;
+
+/// Version of the hashing construction used by [`HashFinder::default`] and [`HashFinder::new`].
+///
+/// Version 1 is the original construction where the target hash is computed over the origin hash alone.
+/// Every other version hashes the version byte before the origin hash, so proofs of different versions
+/// never validate against each other.
+pub const DEFAULT_VERSION: u8 = 1;
+
+/// Number of hashing rounds used by [`HashFinder::default`] and [`HashFinder::new`].
+///
+/// The first round turns the random entropy into the origin hash and the second one turns the origin hash into the
+/// target hash. Every additional round set with [`HashFinder::with_rounds`] hashes the target hash once more.
+pub const DEFAULT_ROUNDS: u32 = 2;
+
 /// `HashFinder` is a Structure for finding cryptographic hashes that meet a specified difficulty target, defined by a number of leading zeros.
 /// The core idea is to search for a hash that is lower than a computed target value.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct HashFinder {
+///
+/// Both rounds of hashing use the digest `D`, which defaults to [`Blake2s256`]. Any digest with a 32-byte output can
+/// be chosen with [`HashFinder::with_digest`] or [`HashFinderBuilder::digest`], and origin hashes found with one digest
+/// only pass the check of a HashFinder using the same digest.
+///
+/// The constructors such as [`HashFinder::new`] always return a Blake2s HashFinder. Making them generic would leave
+/// `D` to be inferred in every existing `HashFinder::new(n).find()` call, since type parameter defaults don't apply
+/// in expressions, so `HashFinder::<Sha256>::new(n)` is spelled `HashFinder::new(n).with_digest::<Sha256>()`. The
+/// digest can also be inferred from a type annotation, which keeps fields and signatures readable.
+///
+/// # Example
+/// ```
+/// use blake2::digest::consts::U32;
+/// use blake2::Blake2b;
+/// use pow_account::HashFinder;
+/// use sha2::Sha256;
+///
+/// let hash_finder = HashFinder::new(3).with_digest::<Blake2b<U32>>();
+/// let origin_hash = hash_finder.find();
+///
+/// assert!(hash_finder.check(hex::encode(origin_hash)).unwrap());
+///
+/// let hash_finder: HashFinder<Sha256> = HashFinder::new(3).with_digest();
+/// let origin_hash = hash_finder.find();
+///
+/// assert!(hash_finder.check_bytes(&origin_hash));
+/// ```
+pub struct HashFinder<D = Blake2s256> {
     target: [u8; 32],
+    floor: [u8; 32],
+    version: u8,
+    counter_salt: bool,
+    rounds: u32,
+    domain: Option<[u8; 32]>,
+    digest: PhantomData<fn() -> D>,
+}
+
+/// Target, floor, version, counter salt, number of rounds and domain tag of a HashFinder.
+type Settings = ([u8; 32], [u8; 32], u8, bool, u32, Option<[u8; 32]>);
+
+impl<D> HashFinder<D> {
+    /// Returns the fields that identify a HashFinder, in the order they are compared
+    fn settings(&self) -> Settings {
+        (
+            self.target,
+            self.floor,
+            self.version,
+            self.counter_salt,
+            self.rounds,
+            self.domain,
+        )
+    }
+}
+
+impl<D> fmt::Debug for HashFinder<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashFinder")
+            .field("target", &self.target)
+            .field("floor", &self.floor)
+            .field("version", &self.version)
+            .field("counter_salt", &self.counter_salt)
+            .field("rounds", &self.rounds)
+            .field("domain", &self.domain)
+            .field("digest", &core::any::type_name::<D>())
+            .finish()
+    }
+}
+
+impl<D> Clone for HashFinder<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for HashFinder<D> {}
+
+impl<D> PartialEq for HashFinder<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.settings() == other.settings()
+    }
+}
+
+impl<D> Eq for HashFinder<D> {}
+
+/// HashFinders are ordered by difficulty: a HashFinder is greater than another if its target is lower, so that
+/// `HashFinder::new(8) > HashFinder::new(4)`. HashFinders with the same target are ordered by their remaining
+/// settings to stay consistent with equality.
+impl<D> PartialOrd for HashFinder<D> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Ord for HashFinder<D> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        other
+            .target
+            .cmp(&self.target)
+            .then_with(|| self.settings().cmp(&other.settings()))
+    }
 }
 
 impl Default for HashFinder {
     fn default() -> Self {
-        HashFinder {
-            target: HashPrefix::default().target(),
-        }
+        HashFinder::with_target(HashPrefix::default().target())
     }
 }
 
 impl HashFinder {
     /// Returns a HashFinder struct with a specified number of target leading zeros
+    ///
+    /// Difficulties of 64 leading zeros and more would leave no target hash below the target, so they saturate at
+    /// [`MAX_DIFFICULTY_BITS`], where no origin hash can realistically be found. Use [`HashFinder::try_new`] to reject
+    /// them instead.
+    ///
     /// # Example
     /// ```
     /// use pow_account::HashFinder;
@@ -113,169 +619,2164 @@ impl HashFinder {
     ///
     /// ```
     pub fn new(leading_zeros: u8) -> Self {
-        HashFinder {
-            target: HashPrefix::new(leading_zeros).target(),
-        }
+        HashFinder::with_bits(4 * u32::from(leading_zeros))
     }
 
-    /// Finds an origin hash
+    /// Returns a HashFinder struct with a specified number of target leading zero bits
     ///
-    /// This function attempts to find a cryptographic hash that is an origin for a target hash that has a specific number of leading zeroes
+    /// Unlike [`HashFinder::new`], which counts leading zeros in hexadecimal digits of 4 bits each, this allows
+    /// the difficulty to be tuned one bit, and therefore one doubling of the expected work, at a time.
+    /// `HashFinder::with_bits(0)` accepts any hash and `HashFinder::new(n)` is equal to `HashFinder::with_bits(4 * n)`.
+    /// Difficulties above [`MAX_DIFFICULTY_BITS`] saturate at it.
     ///
-    /// # Parameters
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
     ///
-    /// - `leading_zeros`: The number of leading zeros to generate in the hash.
+    /// let hash_finder = HashFinder::with_bits(18);
+    /// assert_eq!(hash_finder.expected_attempts().round(), 262144.0);
     ///
-    /// # Returns
+    /// ```
+    pub fn with_bits(bits: u32) -> Self {
+        HashFinder::with_target(HashPrefix::from_bits(bits).target())
+    }
+
+    /// Returns a HashFinder struct with a specified number of target leading zeros and version of the hashing construction
     ///
-    /// This function returns a 32-byte array containing the generated hash.
+    /// Proofs found by a HashFinder of one version never pass the check of a HashFinder of another version,
+    /// even at the same difficulty. `HashFinder::with_version(n, DEFAULT_VERSION)` is equal to `HashFinder::new(n)`.
     ///
     /// # Example
-    ///
-    /// ```rust
+    /// ```
     /// use pow_account::HashFinder;
-    /// use blake2::{Blake2s256, Digest};
-    ///
-    /// let origin_hash = HashFinder::new(4).find();
     ///
-    /// let mut hasher = Blake2s256::new();
-    /// hasher.update(&origin_hash);
-    /// let target_hash: [u8; 32] = hasher.finalize().into();
-    ///
-    /// let target_hash_hex = hex::encode(target_hash);
+    /// let hash_finder = HashFinder::with_version(4, 2);
+    /// assert_eq!(hash_finder.version(), 2);
     ///
-    /// assert!(target_hash_hex.starts_with("0000"));
     /// ```
-    pub fn find(&self) -> [u8; 32] {
-        loop {
-            let origin_hash = Entropy::new().hash();
-            let target_hash = Entropy::from(origin_hash).hash();
-            match target_hash < self.target {
-                true => return origin_hash,
-                false => continue,
-            }
+    pub fn with_version(leading_zeros: u8, version: u8) -> Self {
+        HashFinder {
+            version,
+            ..HashFinder::new(leading_zeros)
         }
     }
 
-    /// Determines whether a given hash serves as the origin for a target hash that satisfies a specified number of leading zeros.
+    /// Returns a HashFinder struct accepting target hashes within a band, `lo <= target_hash < hi`
     ///
-    /// This function takes a hexadecimal string representing an origin hash and checks if
-    /// its hash satisfies the leading zero requirement specified by the `leading_zeros` value.
+    /// The band is enforced by [`HashFinder::find_band`] and [`HashFinder::check_band`], while [`HashFinder::find`]
+    /// and [`HashFinder::check`] keep testing only the upper bound `hi`.
     ///
-    /// # Parameters
+    /// # Returns
     ///
-    /// - `hash`: A string containing the hexadecimal representation of the hash to check.
+    /// This function returns `None` if `lo` is not lower than `hi`.
     ///
-    /// # Returns
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
     ///
-    /// This function returns `Ok(true)` if the hash meets the requirement, otherwise
-    /// it returns `Ok(false)`. If the input string is not a valid hexadecimal representation,
-    /// it returns an `Err` with a description of the error.
+    /// let mut lo = [0u8; 32];
+    /// lo[0] = 0x10;
+    /// let mut hi = [0u8; 32];
+    /// hi[0] = 0x20;
     ///
-    /// # Errors
+    /// assert!(HashFinder::with_band(lo, hi).is_some());
+    /// assert!(HashFinder::with_band(hi, lo).is_none());
     ///
-    /// This function returns an error if the provided hash string is not a valid
-    /// hexadecimal representation.
+    /// ```
+    pub fn with_band(lo: [u8; 32], hi: [u8; 32]) -> Option<Self> {
+        match lo < hi {
+            true => Some(HashFinder {
+                floor: lo,
+                ..HashFinder::with_target(hi)
+            }),
+            false => None,
+        }
+    }
+
+    /// Returns a HashFinder struct that accepts target hashes strictly lower than `target`
     ///
-    /// # Examples
+    /// Together with [`HashFinder::target`] this allows a difficulty to be persisted and restored exactly,
+    /// including targets that don't correspond to a whole number of leading zero bits.
+    ///
+    /// # Example
     /// ```
     /// use pow_account::HashFinder;
     ///
-    /// let origin_hash = HashFinder::new(4).find();
-    /// let origin_hash_hex = hex::encode(origin_hash);
-    /// let result = HashFinder::new(3).check(origin_hash_hex);
-    /// assert!(result.unwrap());
-    ///
-    /// let origin_hash = String::from("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
-    /// let result = HashFinder::new(3).check(origin_hash);
-    /// assert!(result.unwrap());
+    /// let hash_finder = HashFinder::new(4);
+    /// assert_eq!(HashFinder::from_target(hash_finder.target()), hash_finder);
     ///
-    /// let origin_hash = String::from("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e");
-    /// let result = HashFinder::default().check(origin_hash);
-    /// assert!(result.unwrap());
     /// ```
-    pub fn check(&self, origin_hash: String) -> Result<bool, hex::FromHexError> {
-        let mut origin_hash_bytes: [u8; 32] = [0u8; 32];
-        let _ = hex::decode_to_slice(origin_hash, &mut origin_hash_bytes)?;
-
-        let target_hash_bytes = Entropy::from(origin_hash_bytes).hash();
+    pub fn from_target(target: [u8; 32]) -> Self {
+        HashFinder::with_target(target)
+    }
 
-        Ok(target_hash_bytes < self.target)
+    fn with_target(target: [u8; 32]) -> Self {
+        HashFinder {
+            target,
+            floor: [0u8; 32],
+            version: DEFAULT_VERSION,
+            counter_salt: true,
+            rounds: DEFAULT_ROUNDS,
+            domain: None,
+            digest: PhantomData,
+        }
     }
 }
 
-#[cfg(test)]
-mod pow_account {
-
-    use super::*;
-    use hex::FromHexError;
-
-    #[test]
-    fn new_entropy_has_a_length_of_32() {
-        let entropy = Entropy::new().entropy;
-        assert!(entropy.len().eq(&32))
+impl<D> HashFinder<D> {
+    /// Returns a HashFinder with the same settings that hashes with the digest `E`
+    ///
+    /// The digest is used for both rounds of hashing, so the origin hashes found by the returned HashFinder
+    /// only pass the check of a HashFinder using the same digest.
+    ///
+    /// # Example
+    /// ```
+    /// use blake2::digest::consts::U32;
+    /// use blake2::Blake2b;
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(4).with_digest::<Blake2b<U32>>();
+    /// assert_eq!(hash_finder.expected_attempts(), HashFinder::new(4).expected_attempts());
+    ///
+    /// ```
+    pub fn with_digest<E>(self) -> HashFinder<E> {
+        HashFinder {
+            target: self.target,
+            floor: self.floor,
+            version: self.version,
+            counter_salt: self.counter_salt,
+            rounds: self.rounds,
+            domain: self.domain,
+            digest: PhantomData,
+        }
     }
 
-    #[test]
-    fn new_entropy_is_unique() {
-        let entropy_a = Entropy::new().entropy;
-        let entropy_b = Entropy::new().entropy;
-        assert_ne!(entropy_a, entropy_b)
+    /// Returns a HashFinder with the same settings that chains `rounds` rounds of hashing
+    ///
+    /// The first round hashes the random entropy into the origin hash and every following round hashes the output of
+    /// the previous one, the last output being compared with the target. Each additional round makes both finding and
+    /// checking proportionally more expensive. Proofs found with one number of rounds don't pass the check of a
+    /// HashFinder with another one. Values below [`DEFAULT_ROUNDS`] are raised to it, since the origin hash has to be
+    /// hashed at least once to prove any work.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(3).with_rounds(4);
+    /// let origin_hash = hash_finder.find();
+    ///
+    /// assert_eq!(hash_finder.rounds(), 4);
+    /// assert!(hash_finder.check_bytes(&origin_hash));
+    ///
+    /// ```
+    pub fn with_rounds(self, rounds: u32) -> Self {
+        HashFinder {
+            rounds: rounds.max(DEFAULT_ROUNDS),
+            ..self
+        }
     }
 
-    #[test]
-    fn entropy_generates_256bit_hash() {
-        let hash = Entropy::new().hash();
-        assert!(hash.len().eq(&32))
+    /// Returns the number of hashing rounds
+    pub fn rounds(&self) -> u32 {
+        self.rounds
     }
 
-    #[test]
-    fn entropy_created_from_a_set_of_bytes() {
-        let entropy_a = Entropy::new().entropy;
-        let entropy_b = Entropy::from(entropy_a);
-        assert_eq!(entropy_b.entropy, entropy_a)
+    /// Returns the 256-bit target that target hashes have to be strictly lower than, in big-endian order
+    pub fn target(&self) -> [u8; 32] {
+        self.target
     }
 
-    #[test]
-    fn blake2s_hash_can_be_validated() {
-        let origin_hash = "c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa";
-        let target_hash = "7478987293e1864fd833ae3607bc99b9b22e7ca39bced21c3b0428bd9c7218ba";
-
-        let origin_hash_vec = hex::decode(origin_hash).unwrap();
-        let origin_hash_bytes: [u8; 32] = origin_hash_vec.try_into().unwrap();
-
-        let entropy = Entropy::from(origin_hash_bytes);
-        let origin_hash_hex = hex::encode(entropy.hash()).to_string();
-        assert_eq!(origin_hash_hex, target_hash)
+    /// Returns the version of the hashing construction
+    pub fn version(&self) -> u8 {
+        self.version
     }
 
-    #[test]
-    fn new_hash_prefix_has_non_zero_number_of_bits() {
-        let hash_prefix = HashPrefix::default();
-        assert!(hash_prefix.get() > 0)
+    /// Changes the number of target leading zeros of an existing HashFinder
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let mut hash_finder = HashFinder::default();
+    /// hash_finder.set_difficulty(4);
+    /// assert_eq!(hash_finder, HashFinder::new(4));
+    ///
+    /// ```
+    pub fn set_difficulty(&mut self, leading_zeros: u8) {
+        self.target = HashPrefix::new(leading_zeros).target();
     }
 
-    #[test]
-    fn default_target_matches_max_value() {
-        let target = HashPrefix::default().target();
-        let max_target: [u8; 32] = [
-            0x00, 0x00, 0x0f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff,
-        ];
-
-        assert_eq!(target, max_target)
+    /// Enables or disables the counter salt, which is enabled by default
+    ///
+    /// With the counter salt, an incrementing counter is appended to the random entropy of every attempt before the
+    /// origin hash is computed. Even if the random number generator repeats its output, every attempt then hashes a
+    /// different input, which makes finding more robust to low-quality randomness. Verification is not affected:
+    /// origin hashes found with and without the counter salt are checked the same way.
+    ///
+    /// Every search reserves the start of its counter once from a process-wide counter and increments it locally, so
+    /// parallel searches don't contend on it. The 8 bytes of the counter keep the input within a single block of
+    /// Blake2s and SHA-256, so the salt costs a copy and an increment per attempt rather than an extra compression.
+    ///
+    /// [`HashFinder::find_with_rng`] starts its counter at 0 instead, to stay reproducible.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let mut hash_finder = HashFinder::new(3);
+    /// hash_finder.set_counter_salt(false);
+    /// assert!(!hash_finder.counter_salt());
+    ///
+    /// ```
+    pub fn set_counter_salt(&mut self, enabled: bool) {
+        self.counter_salt = enabled;
     }
 
-    #[test]
-    fn can_find_a_hash_which_starts_from_a_specific_pattern() {
-        let origin_hash = HashFinder::new(4).find();
-        let target_hash = Entropy::from(origin_hash).hash();
+    /// Returns whether the counter salt is enabled
+    pub fn counter_salt(&self) -> bool {
+        self.counter_salt
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Returns a HashFinder with the same settings that binds its proofs to an application domain
+    ///
+    /// A tag derived from `domain` is mixed into the target hash, both when finding and when checking, so proofs found
+    /// for one domain never pass the check of a HashFinder bound to another domain or to no domain at all. Use a fixed
+    /// identifier per deployment, such as the name of the service, to keep proofs from being reused across
+    /// applications. The tag is computed with the digest of this HashFinder, so call [`HashFinder::with_digest`]
+    /// first when both are needed.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let service_a = HashFinder::new(3).with_domain(b"service-a");
+    /// let service_b = HashFinder::new(3).with_domain(b"service-b");
+    ///
+    /// let origin_hash = service_a.find();
+    /// assert!(service_a.check_bytes(&origin_hash));
+    /// assert_ne!(service_a, service_b);
+    ///
+    /// ```
+    pub fn with_domain(self, domain: &[u8]) -> Self {
+        HashFinder {
+            domain: Some(domain_tag::<D>(domain)),
+            ..self
+        }
+    }
+
+    /// Finds an origin hash
+    ///
+    /// This function attempts to find a cryptographic hash that is an origin for a target hash that has a specific number of leading zeroes
+    ///
+    /// # Parameters
+    ///
+    /// - `leading_zeros`: The number of leading zeros to generate in the hash.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    /// use blake2::{Blake2s256, Digest};
+    ///
+    /// let origin_hash = HashFinder::new(4).find();
+    ///
+    /// let mut hasher = Blake2s256::new();
+    /// hasher.update(&origin_hash);
+    /// let target_hash: [u8; 32] = hasher.finalize().into();
+    ///
+    /// let target_hash_hex = hex::encode(target_hash);
+    ///
+    /// assert!(target_hash_hex.starts_with("0000"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find(&self) -> [u8; 32] {
+        self.find_with_progress(|_| {})
+    }
+
+    /// Finds an origin hash and returns its hexadecimal representation
+    ///
+    /// This is the format [`HashFinder::check`] expects, so the result can be sent to a verifier as is.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the 64 lowercase hexadecimal characters of the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(3);
+    /// assert!(hash_finder.check(hash_finder.find_hex()).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_hex(&self) -> String {
+        hex::encode(self.find())
+    }
+
+    /// Finds an origin hash and periodically reports how many candidates have been hashed so far
+    ///
+    /// The callback is invoked every [`PROGRESS_INTERVAL`] attempts with the running number of attempts, so it can
+    /// drive a spinner or a live hash rate without slowing the search down. It is not invoked at all if an origin
+    /// hash is found within the first interval. [`HashFinder::find`] is equal to `find_with_progress(|_| {})`.
+    ///
+    /// # Parameters
+    ///
+    /// - `on_progress`: The callback receiving the number of attempts made so far.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let mut reports = Vec::new();
+    /// let origin_hash = HashFinder::new(3).find_with_progress(|attempts| reports.push(attempts));
+    ///
+    /// assert!(reports.iter().all(|attempts| attempts % 100_000 == 0));
+    /// assert!(HashFinder::new(3).check_bytes(&origin_hash));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_with_progress<F: FnMut(u64)>(&self, mut on_progress: F) -> [u8; 32] {
+        self.find_bounded(
+            &mut OsRng,
+            &mut SaltCounter::reserve(),
+            u64::MAX,
+            &mut on_progress,
+        )
+        .expect("no origin hash found in 2^64 attempts")
+        .0
+    }
+
+    /// Finds an origin hash using entropy drawn from a given random number generator
+    ///
+    /// [`HashFinder::find`] draws its entropy from `OsRng` the same way. Unlike the other searches, the counter salt
+    /// of `find_with_rng` starts at 0 on every call instead of being reserved from the process-wide counter, so with a
+    /// seeded generator the search is reproducible whatever the settings, which is useful for tests and fuzzing.
+    ///
+    /// # Parameters
+    ///
+    /// - `rng`: The random number generator the entropy of each attempt is drawn from.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let hash_finder = HashFinder::new(3);
+    ///
+    /// let origin_hash = hash_finder.find_with_rng(&mut StdRng::seed_from_u64(7));
+    /// assert_eq!(origin_hash, hash_finder.find_with_rng(&mut StdRng::seed_from_u64(7)));
+    /// ```
+    pub fn find_with_rng<R: RngCore>(&self, rng: &mut R) -> [u8; 32] {
+        self.find_bounded(rng, &mut SaltCounter::from_zero(), u64::MAX, &mut |_| {})
+            .expect("no origin hash found in 2^64 attempts")
+            .0
+    }
+
+    /// Finds an origin hash and reports how many candidates were hashed
+    ///
+    /// # Returns
+    ///
+    /// This function returns the origin hash together with the number of candidates hashed, including the successful
+    /// one. On average the number of attempts is [`HashFinder::expected_attempts`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let (origin_hash, attempts) = HashFinder::new(3).find_with_attempts();
+    ///
+    /// assert!(attempts >= 1);
+    /// assert!(HashFinder::new(3).check(hex::encode(origin_hash)).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_with_attempts(&self) -> ([u8; 32], u64) {
+        self.find_bounded(
+            &mut OsRng,
+            &mut SaltCounter::reserve(),
+            u64::MAX,
+            &mut |_| {},
+        )
+        .expect("no origin hash found in 2^64 attempts")
+    }
+
+    /// Finds an origin hash, giving up after a maximum number of attempts
+    ///
+    /// The search runs on the calling thread and never reads the clock, which makes it the entry point to use in
+    /// browsers with the `wasm` feature: a bounded number of attempts keeps the page responsive where a timeout
+    /// can't be measured.
+    ///
+    /// # Parameters
+    ///
+    /// - `max_attempts`: The maximum number of candidates to hash. With `0` nothing is hashed.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Some` origin hash, or `None` if none of the `max_attempts` candidates meets the
+    /// requirement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// assert_eq!(HashFinder::new(8).find_with_limit(0), None);
+    ///
+    /// if let Some(origin_hash) = HashFinder::new(2).find_with_limit(100_000) {
+    ///     assert!(HashFinder::new(2).check(hex::encode(origin_hash)).unwrap());
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_with_limit(&self, max_attempts: u64) -> Option<[u8; 32]> {
+        self.find_bounded(
+            &mut OsRng,
+            &mut SaltCounter::reserve(),
+            max_attempts,
+            &mut |_| {},
+        )
+        .map(|(origin_hash, _)| origin_hash)
+    }
+
+    /// Finds an origin hash, giving up once a timeout has elapsed
+    ///
+    /// The clock is only read every few thousand attempts so that it doesn't slow the search down, which means the
+    /// function can return slightly after the deadline.
+    ///
+    /// # Parameters
+    ///
+    /// - `timeout`: The maximum duration of the search.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Some` origin hash, or `None` if none was found before the deadline.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::HashFinder;
+    ///
+    /// if let Some(origin_hash) = HashFinder::new(3).find_with_timeout(Duration::from_secs(1)) {
+    ///     assert!(HashFinder::new(3).check(hex::encode(origin_hash)).unwrap());
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_with_timeout(&self, timeout: Duration) -> Option<[u8; 32]> {
+        let started_at = Instant::now();
+        let mut counter = SaltCounter::reserve();
+
+        while started_at.elapsed() < timeout {
+            let found = self.find_bounded(&mut OsRng, &mut counter, POLL_INTERVAL, &mut |_| {});
+            if let Some((origin_hash, _)) = found {
+                return Some(origin_hash);
+            }
+        }
+        None
+    }
+
+    /// Finds an origin hash, giving up once a cancellation flag is set
+    ///
+    /// The flag is read every few thousand attempts, so a search observes the cancellation shortly after the flag
+    /// is set. Sharing one flag, for example in an `Arc<AtomicBool>`, lets a single store abort several searches.
+    ///
+    /// # Parameters
+    ///
+    /// - `cancel`: The flag that stops the search when it is set to `true`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Some` origin hash, or `None` if the search was cancelled first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::atomic::AtomicBool;
+    /// use pow_account::HashFinder;
+    ///
+    /// let cancel = AtomicBool::new(true);
+    /// assert_eq!(HashFinder::new(8).find_cancellable(&cancel), None);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_cancellable(&self, cancel: &AtomicBool) -> Option<[u8; 32]> {
+        let mut counter = SaltCounter::reserve();
+
+        while !cancel.load(Ordering::Relaxed) {
+            let found = self.find_bounded(&mut OsRng, &mut counter, POLL_INTERVAL, &mut |_| {});
+            if let Some((origin_hash, _)) = found {
+                return Some(origin_hash);
+            }
+        }
+        None
+    }
+
+    fn find_bounded<R: RngCore, F: FnMut(u64)>(
+        &self,
+        rng: &mut R,
+        counter: &mut SaltCounter,
+        max_attempts: u64,
+        on_progress: &mut F,
+    ) -> Option<([u8; 32], u64)> {
+        for attempts in 1..=max_attempts {
+            if let Some(origin_hash) = self.attempt(rng, counter) {
+                return Some((origin_hash, attempts));
+            }
+            if attempts % PROGRESS_INTERVAL == 0 {
+                on_progress(attempts);
+            }
+        }
+        None
+    }
+
+    /// Determines whether a given hash serves as the origin for a target hash that satisfies a specified number of leading zeros.
+    ///
+    /// This function takes a hexadecimal string representing an origin hash and checks if
+    /// its hash satisfies the leading zero requirement specified by the `leading_zeros` value.
+    /// Any string type is accepted, so a `&str` borrowed from a larger buffer doesn't have to be copied into a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// - `hash`: A string or byte slice containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Ok(true)` if the hash meets the requirement, otherwise
+    /// it returns `Ok(false)`. If the input string is not a valid hexadecimal representation,
+    /// it returns an `Err` with a description of the error.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    ///
+    /// # Examples
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::new(4).find();
+    /// let origin_hash_hex = hex::encode(origin_hash);
+    /// let result = HashFinder::new(3).check(origin_hash_hex);
+    /// assert!(result.unwrap());
+    ///
+    /// let origin_hash = String::from("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
+    /// let result = HashFinder::new(3).check(origin_hash);
+    /// assert!(result.unwrap());
+    ///
+    /// let origin_hash = String::from("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e");
+    /// let result = HashFinder::default().check(origin_hash);
+    /// assert!(result.unwrap());
+    ///
+    /// let result = HashFinder::new(3).check("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
+    /// assert!(result.unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn check<S: AsRef<[u8]>>(&self, origin_hash: S) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+
+        Ok(self.check_bytes(&origin_hash_bytes))
+    }
+
+    /// Determines whether an already decoded origin hash meets the requirement
+    ///
+    /// This is the same check as [`HashFinder::check`] without the hexadecimal decoding, for callers that
+    /// receive origin hashes as raw bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(3);
+    /// let origin_hash = hash_finder.find();
+    ///
+    /// assert!(hash_finder.check_bytes(&origin_hash));
+    /// ```
+    pub fn check_bytes(&self, origin_hash: &[u8; 32]) -> bool {
+        self.meets_target(&self.target_hash(*origin_hash))
+    }
+
+    /// Finds an origin hash whose target hash falls within the band of [`HashFinder::with_band`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let mut lo = [0u8; 32];
+    /// lo[0] = 0x10;
+    /// let mut hi = [0u8; 32];
+    /// hi[0] = 0x20;
+    ///
+    /// let hash_finder = HashFinder::with_band(lo, hi).unwrap();
+    /// let origin_hash = hash_finder.find_band();
+    ///
+    /// assert!(hash_finder.check_band(hex::encode(origin_hash)).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_band(&self) -> [u8; 32] {
+        let mut counter = SaltCounter::reserve();
+        loop {
+            let origin_hash = self.origin_candidate(&mut counter);
+            if self.in_band(&self.target_hash(origin_hash)) {
+                return origin_hash;
+            }
+        }
+    }
+
+    /// Determines whether a given origin hash produces a target hash within the band of [`HashFinder::with_band`].
+    ///
+    /// # Parameters
+    ///
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    #[cfg(feature = "std")]
+    pub fn check_band<S: AsRef<[u8]>>(&self, origin_hash: S) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+
+        Ok(self.in_band(&self.target_hash(origin_hash_bytes)))
+    }
+
+    #[cfg(feature = "std")]
+    fn in_band(&self, target_hash: &[u8; 32]) -> bool {
+        self.floor <= *target_hash && self.meets_target(target_hash)
+    }
+
+    /// Finds an origin hash in bursts separated by pauses
+    ///
+    /// The search hashes `attempts_per_burst` candidates, sleeps for `pause`, and repeats until an origin hash is
+    /// found. This keeps the average CPU load down on battery-powered or thermally constrained devices at the cost
+    /// of a longer search.
+    ///
+    /// # Parameters
+    ///
+    /// - `attempts_per_burst`: The number of candidates hashed between two pauses, at least one per burst.
+    /// - `pause`: How long to sleep after every unsuccessful burst.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::new(2).find_paced(64, Duration::from_millis(1));
+    /// assert!(HashFinder::new(2).check(hex::encode(origin_hash)).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_paced(&self, attempts_per_burst: u64, pause: Duration) -> [u8; 32] {
+        let mut counter = SaltCounter::reserve();
+        loop {
+            for _ in 0..attempts_per_burst.max(1) {
+                if let Some(origin_hash) = self.attempt(&mut OsRng, &mut counter) {
+                    return origin_hash;
+                }
+            }
+            std::thread::sleep(pause);
+        }
+    }
+
+    /// Finds an origin hash using entropy carved out of a pre-filled random buffer
+    ///
+    /// Instead of reading 32 random bytes from the operating system on every attempt, a buffer of `buffer_bytes` is
+    /// filled at once and consumed 32 bytes at a time, and it is refilled only when all of its candidates have been
+    /// tried. This amortizes the cost of the random number generator over many attempts.
+    ///
+    /// # Parameters
+    ///
+    /// - `buffer_bytes`: The size of the random buffer, rounded down to a multiple of 32 bytes with a minimum of 32.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::new(3).find_buffered(4096);
+    /// assert!(HashFinder::new(3).check(hex::encode(origin_hash)).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_buffered(&self, buffer_bytes: usize) -> [u8; 32] {
+        let mut buffer = vec![0u8; (buffer_bytes / 32).max(1) * 32];
+        let mut counter = SaltCounter::reserve();
+
+        loop {
+            OsRng.fill_bytes(&mut buffer);
+
+            for chunk in buffer.chunks_exact(32) {
+                let mut entropy = [0u8; 32];
+                entropy.copy_from_slice(chunk);
+
+                let origin_hash = self.origin_from(Entropy::from(entropy), &mut counter);
+                if self.meets_target(&self.target_hash(origin_hash)) {
+                    return origin_hash;
+                }
+            }
+        }
+    }
+
+    /// Finds several independent origin hashes
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hashes = HashFinder::new(2).find_batch(3);
+    /// assert_eq!(origin_hashes.len(), 3);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_batch(&self, count: usize) -> Vec<[u8; 32]> {
+        self.iter().take(count).collect()
+    }
+
+    /// Returns an endless iterator of independent origin hashes
+    ///
+    /// Every call to `next` runs a new search with fresh entropy, as [`HashFinder::find`] does, so the hashes are
+    /// found lazily as they are consumed. The iterator holds a copy of the HashFinder and can be sent to another thread.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(2);
+    /// let origin_hashes: Vec<[u8; 32]> = hash_finder.iter().take(3).collect();
+    ///
+    /// for origin_hash in origin_hashes {
+    ///     assert!(hash_finder.check_bytes(&origin_hash));
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn iter(&self) -> impl Iterator<Item = [u8; 32]> + Send {
+        let hash_finder = *self;
+        std::iter::repeat_with(move || hash_finder.find())
+    }
+
+    /// Finds several origin hashes and sends them to a bounded channel
+    ///
+    /// Sending blocks while the channel is full, so a slow consumer naturally holds the search back instead of
+    /// letting found hashes pile up in memory. The search stops as soon as the receiver is disconnected.
+    ///
+    /// # Parameters
+    ///
+    /// - `tx`: The sending half of a channel created with [`std::sync::mpsc::sync_channel`].
+    /// - `count`: The number of origin hashes to find.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the number of origin hashes delivered to the channel, which is lower than `count`
+    /// only if the receiver was disconnected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::mpsc::sync_channel;
+    /// use std::thread;
+    /// use pow_account::HashFinder;
+    ///
+    /// let (tx, rx) = sync_channel(1);
+    /// let producer = thread::spawn(move || HashFinder::new(2).find_to_sync_channel(tx, 3));
+    ///
+    /// for origin_hash in rx {
+    ///     assert!(HashFinder::new(2).check(hex::encode(origin_hash)).unwrap());
+    /// }
+    /// assert_eq!(producer.join().unwrap(), 3);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_to_sync_channel(&self, tx: SyncSender<[u8; 32]>, count: usize) -> usize {
+        for sent in 0..count {
+            if tx.send(self.find()).is_err() {
+                return sent;
+            }
+        }
+        count
+    }
+
+    /// Finds several independent origin hashes sorted by the work they achieved, best first
+    ///
+    /// # Returns
+    ///
+    /// This function returns each origin hash paired with the number of leading zeros of its target hash,
+    /// as reported by [`HashFinder::check_difficulty`], in descending order of leading zeros.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let ranked = HashFinder::new(2).find_batch_ranked(3);
+    /// assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_batch_ranked(&self, count: usize) -> Vec<([u8; 32], u32)> {
+        let mut ranked: Vec<([u8; 32], u32)> = self
+            .find_batch(count)
+            .into_iter()
+            .map(|origin_hash| (origin_hash, self.check_difficulty(&origin_hash)))
+            .collect();
+
+        ranked.sort_by_key(|(_, leading_zeros)| std::cmp::Reverse(*leading_zeros));
+        ranked
+    }
+
+    /// Returns the number of leading zeros of the target hash produced by an origin hash
+    ///
+    /// The value is expressed in the same unit as [`HashFinder::new`], so an origin hash that passes
+    /// `HashFinder::new(n)` has at least `n` leading zeros.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::new(3).find();
+    /// assert!(HashFinder::default().check_difficulty(&origin_hash) >= 3);
+    /// ```
+    pub fn check_difficulty(&self, origin_hash: &[u8; 32]) -> u32 {
+        target_difficulty(&self.target_hash(*origin_hash))
+    }
+
+    /// Finds an origin hash bound to a pre-hashed payload
+    ///
+    /// The target hash is computed over the payload digest followed by the origin hash, so the resulting origin hash
+    /// only passes [`HashFinder::check_with_prehash`] for the same payload digest. Because only the 32-byte digest is mixed
+    /// into each attempt, large payloads are hashed once with [`payload_prehash`] rather than on every attempt.
+    ///
+    /// # Parameters
+    ///
+    /// - `payload_hash`: The Blake2s digest of the payload, as returned by [`payload_prehash`].
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::{payload_prehash, HashFinder};
+    ///
+    /// let payload_hash = payload_prehash(b"account-request");
+    /// let origin_hash = HashFinder::new(3).find_with_prehash(&payload_hash);
+    ///
+    /// let result = HashFinder::new(3).check_with_prehash(&payload_hash, hex::encode(origin_hash));
+    /// assert!(result.unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_with_prehash(&self, payload_hash: &[u8; 32]) -> [u8; 32] {
+        self.find_bound(Binding::Prehash, payload_hash)
+    }
+
+    /// Finds an origin hash whose target hash is bound to `prefix`, as checked with [`HashFinder::bound_target_hash`].
+    #[cfg(feature = "std")]
+    pub(crate) fn find_bound(&self, binding: Binding, prefix: &[u8; 32]) -> [u8; 32] {
+        let mut counter = SaltCounter::reserve();
+        loop {
+            let origin_hash = self.origin_candidate(&mut counter);
+            let target_hash = self.bound_target_hash(binding, prefix, origin_hash);
+            if self.meets_target(&target_hash) {
+                return origin_hash;
+            }
+        }
+    }
+
+    /// Determines whether a given origin hash was found for a pre-hashed payload with [`HashFinder::find_with_prehash`].
+    ///
+    /// # Parameters
+    ///
+    /// - `payload_hash`: The Blake2s digest of the payload, as returned by [`payload_prehash`].
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    #[cfg(feature = "std")]
+    pub fn check_with_prehash<S: AsRef<[u8]>>(
+        &self,
+        payload_hash: &[u8; 32],
+        origin_hash: S,
+    ) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+
+        let target_hash_bytes =
+            self.bound_target_hash(Binding::Prehash, payload_hash, origin_hash_bytes);
+
+        Ok(self.meets_target(&target_hash_bytes))
+    }
+
+    /// Finds an origin hash that is valid under two application domains at once
+    ///
+    /// For each domain the target hash is the one of a HashFinder bound to that domain with [`HashFinder::with_domain`]
+    /// instead of the domain of this one, and both target hashes have to be lower than the target, so the origin hash
+    /// passes the checks of both. The two checks are independent, so the expected number of
+    /// attempts is the square of [`HashFinder::expected_attempts`]: a difficulty that takes 2^16 attempts for a single
+    /// domain takes about 2^32 attempts for two.
+    ///
+    /// # Parameters
+    ///
+    /// - `domain_a`: The identifier of the first application.
+    /// - `domain_b`: The identifier of the second application.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(1);
+    /// let origin_hash = hash_finder.find_dual_domain(b"service-a", b"service-b");
+    ///
+    /// let result = hash_finder.check_dual_domain(b"service-a", b"service-b", hex::encode(origin_hash));
+    /// assert!(result.unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_dual_domain(&self, domain_a: &[u8], domain_b: &[u8]) -> [u8; 32] {
+        let domain_tag_a = domain_tag::<D>(domain_a);
+        let domain_tag_b = domain_tag::<D>(domain_b);
+
+        let mut counter = SaltCounter::reserve();
+        loop {
+            let origin_hash = self.origin_candidate(&mut counter);
+            if self.meets_dual_domain(&domain_tag_a, &domain_tag_b, origin_hash) {
+                return origin_hash;
+            }
+        }
+    }
+
+    /// Determines whether a given origin hash was found for both domains with [`HashFinder::find_dual_domain`].
+    ///
+    /// # Parameters
+    ///
+    /// - `domain_a`: The identifier of the first application.
+    /// - `domain_b`: The identifier of the second application.
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    #[cfg(feature = "std")]
+    pub fn check_dual_domain<S: AsRef<[u8]>>(
+        &self,
+        domain_a: &[u8],
+        domain_b: &[u8],
+        origin_hash: S,
+    ) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+
+        Ok(self.meets_dual_domain(
+            &domain_tag::<D>(domain_a),
+            &domain_tag::<D>(domain_b),
+            origin_hash_bytes,
+        ))
+    }
+
+    #[cfg(feature = "std")]
+    fn meets_dual_domain(
+        &self,
+        domain_tag_a: &[u8; 32],
+        domain_tag_b: &[u8; 32],
+        origin_hash: [u8; 32],
+    ) -> bool {
+        let bound_to = |domain_tag: &[u8; 32]| HashFinder {
+            domain: Some(*domain_tag),
+            ..*self
+        };
+
+        bound_to(domain_tag_a).check_bytes(&origin_hash)
+            && bound_to(domain_tag_b).check_bytes(&origin_hash)
+    }
+
+    /// Finds an origin hash for whichever of several difficulties is solved first
+    ///
+    /// The search draws candidates from a single entropy stream and tests each one against the next difficulty
+    /// in turn, so every difficulty receives one attempt per round. This models a worker hedging across tiers:
+    /// lower difficulties are naturally solved more often, but every tier gets an equal share of the work.
+    ///
+    /// Each tier is `self` with the target of [`HashFinder::new`] for its number of leading zeros and no floor, so
+    /// the version, rounds, domain tag and digest of `self` apply to every tier.
+    ///
+    /// # Parameters
+    ///
+    /// - `difficulties`: The numbers of leading zeros to search for, in the order they are attempted.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the solved number of leading zeros together with its origin hash, which passes
+    /// [`HashFinder::check`] on the tier of that number of leading zeros.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `difficulties` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::default().with_rounds(3);
+    /// let (leading_zeros, origin_hash) = hash_finder.find_round_robin(&[2, 3]);
+    ///
+    /// let tier = HashFinder::new(leading_zeros).with_rounds(3);
+    /// assert!(tier.check(hex::encode(origin_hash)).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_round_robin(&self, difficulties: &[u8]) -> (u8, [u8; 32]) {
+        assert!(
+            !difficulties.is_empty(),
+            "at least one difficulty is required"
+        );
+
+        let tiers: Vec<(u8, HashFinder<D>)> = difficulties
+            .iter()
+            .map(|&leading_zeros| {
+                let tier = HashFinder {
+                    target: HashPrefix::new(leading_zeros).target(),
+                    floor: [0u8; 32],
+                    ..*self
+                };
+                (leading_zeros, tier)
+            })
+            .collect();
+
+        let mut counter = SaltCounter::reserve();
+        loop {
+            for (leading_zeros, tier) in tiers.iter() {
+                let origin_hash = self.origin_candidate(&mut counter);
+                if tier.meets_target(&self.target_hash(origin_hash)) {
+                    return (*leading_zeros, origin_hash);
+                }
+            }
+        }
+    }
+
+    /// Returns the expected number of attempts needed to find an origin hash
+    ///
+    /// The value is derived from the stored target: a random target hash is accepted with a probability of
+    /// `target / 2^256`, and the expected number of attempts is the reciprocal of that probability.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let expected_attempts = HashFinder::new(4).expected_attempts();
+    /// assert_eq!(expected_attempts.round(), 65536.0);
+    /// ```
+    pub fn expected_attempts(&self) -> f64 {
+        1.0 / self.accept_probability()
+    }
+
+    /// Returns the expected duration of a search at a given hash rate
+    ///
+    /// The duration is [`HashFinder::expected_attempts`] divided by `hashes_per_second`, which can be measured with
+    /// [`benchmark_hashrate`]. Individual searches vary widely around this average.
+    ///
+    /// # Returns
+    ///
+    /// This function returns [`Duration::MAX`] if the expected duration doesn't fit in a `Duration` or if
+    /// `hashes_per_second` is not positive.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::HashFinder;
+    ///
+    /// let duration = HashFinder::with_bits(28).estimate_duration(2_000_000.0);
+    /// assert_eq!(duration.as_secs(), 134);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn estimate_duration(&self, hashes_per_second: f64) -> Duration {
+        Duration::try_from_secs_f64(self.expected_attempts() / hashes_per_second)
+            .unwrap_or(Duration::MAX)
+    }
+
+    /// Returns the probability that a random origin hash is accepted
+    ///
+    /// The probability is the fraction of the hash space below the stored target, `target / 2^256`,
+    /// which is roughly `2^-bits` for a target with `bits` leading zero bits. It is the reciprocal of
+    /// [`HashFinder::expected_attempts`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let accept_probability = HashFinder::new(1).accept_probability();
+    /// assert!((accept_probability - 1.0 / 16.0).abs() < 1e-12);
+    /// ```
+    pub fn accept_probability(&self) -> f64 {
+        self.target.iter().rev().fold(0.0, |probability, byte| {
+            (probability + *byte as f64) / 256.0
+        })
+    }
+
+    /// Finds a batch of origin hashes from a deterministic counter so that the search can be resumed
+    ///
+    /// Instead of drawing fresh entropy for every attempt, the candidate entropy for index `i` is `base`
+    /// with its last 8 bytes XORed with the big-endian representation of `i`. The same `base` and
+    /// `start_index` therefore always produce the same origin hashes, and an interrupted job can
+    /// continue by passing the saved `base` together with the returned index.
+    ///
+    /// # Parameters
+    ///
+    /// - `base`: The 32 bytes of entropy the counter is mixed into. Keep it random and secret until the batch is done.
+    /// - `start_index`: The first counter value to try.
+    /// - `count`: The number of origin hashes to find.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the found origin hashes and the counter value to resume from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(2);
+    /// let base = [7u8; 32];
+    ///
+    /// let (first, next_index) = hash_finder.find_batch_resumable(base, 0, 2);
+    /// let (second, _) = hash_finder.find_batch_resumable(base, next_index, 2);
+    ///
+    /// for origin_hash in first.iter().chain(second.iter()) {
+    ///     assert!(hash_finder.check(hex::encode(origin_hash)).unwrap());
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_batch_resumable(
+        &self,
+        base: [u8; 32],
+        start_index: u64,
+        count: usize,
+    ) -> (Vec<[u8; 32]>, u64) {
+        let mut origin_hashes = Vec::with_capacity(count);
+        let mut index = start_index;
+
+        while origin_hashes.len() < count {
+            let origin_hash = self.counter_attempt(base, index);
+            index = index.wrapping_add(1);
+            origin_hashes.extend(origin_hash);
+        }
+
+        (origin_hashes, index)
+    }
+
+    /// Finds an origin hash by incrementing a counter in a single draw of random entropy
+    ///
+    /// [`HashFinder::find`] draws fresh entropy from the operating system for every attempt, which dominates the cost
+    /// of an attempt at low difficulties. This search draws 32 random bytes once and derives the entropy of every
+    /// candidate from them and a counter, as [`HashFinder::find_batch_resumable`] does, so the random number generator
+    /// is only used once per search. The origin hashes are checked exactly like the ones found by
+    /// [`HashFinder::find`]. The `search` benchmark compares the throughput of both.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::new(3).find_counter();
+    /// assert!(HashFinder::new(3).check(hex::encode(origin_hash)).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_counter(&self) -> [u8; 32] {
+        let mut base = [0u8; 32];
+        OsRng.fill_bytes(&mut base);
+
+        (0..=u64::MAX)
+            .find_map(|index| self.counter_attempt(base, index))
+            .expect("no origin hash found in 2^64 attempts")
+    }
+
+    /// Finds the first origin hash of the counter search from `seed`, so that the same seed always yields the same
+    /// origin hash
+    ///
+    /// Candidate `i`, from 0, is the hash of `seed` with its last 8 bytes XORed with the big-endian representation of
+    /// `i`, as in [`HashFinder::find_counter`], and the first candidate that meets the target is returned. The result
+    /// only depends on the seed and on the settings of the HashFinder, not on the counter salt, the process or the
+    /// platform, so it can be pinned in snapshot tests and reproduced by other implementations. This also works
+    /// without `std`.
+    ///
+    /// A seed known in advance makes the proof known in advance: outside of tests, draw it at random.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(3);
+    /// let origin_hash = hash_finder.find_seeded([7u8; 32]);
+    ///
+    /// assert_eq!(origin_hash, hash_finder.find_seeded([7u8; 32]));
+    /// assert!(hash_finder.check_bytes(&origin_hash));
+    /// ```
+    pub fn find_seeded(&self, seed: [u8; 32]) -> [u8; 32] {
+        (0..=u64::MAX)
+            .find_map(|index| self.counter_attempt(seed, index))
+            .expect("no origin hash found in 2^64 attempts")
+    }
+
+    fn counter_attempt(&self, base: [u8; 32], index: u64) -> Option<[u8; 32]> {
+        let origin_hash = Entropy::from_counter(base, index).hash::<D>();
+        match self.meets_target(&self.target_hash(origin_hash)) {
+            true => Some(origin_hash),
+            false => None,
+        }
+    }
+
+    fn attempt<R: RngCore>(&self, rng: &mut R, counter: &mut SaltCounter) -> Option<[u8; 32]> {
+        let origin_hash = self.origin_from(Entropy::from_rng(rng), counter);
+        match self.meets_target(&self.target_hash(origin_hash)) {
+            true => Some(origin_hash),
+            false => None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn origin_candidate(&self, counter: &mut SaltCounter) -> [u8; 32] {
+        self.origin_from(Entropy::new(), counter)
+    }
+
+    fn origin_from(&self, entropy: Entropy, counter: &mut SaltCounter) -> [u8; 32] {
+        match self.counter_salt {
+            true => entropy.hash_with_suffix::<D>(&counter.next().to_be_bytes()),
+            false => entropy.hash::<D>(),
+        }
+    }
+
+    fn meets_target(&self, target_hash: &[u8; 32]) -> bool {
+        ct_lt(target_hash, &self.target)
+    }
+
+    fn target_hash(&self, origin_hash: [u8; 32]) -> [u8; 32] {
+        self.framed_target_hash(None, origin_hash)
+    }
+
+    /// Returns the target hash of an origin hash bound to the 32 bytes of `prefix`, such as a challenge
+    pub(crate) fn bound_target_hash(
+        &self,
+        binding: Binding,
+        prefix: &[u8; 32],
+        origin_hash: [u8; 32],
+    ) -> [u8; 32] {
+        self.framed_target_hash(Some((binding, prefix)), origin_hash)
+    }
+
+    fn framed_target_hash(
+        &self,
+        binding: Option<(Binding, &[u8; 32])>,
+        origin_hash: [u8; 32],
+    ) -> [u8; 32] {
+        let entropy = Entropy::from(origin_hash);
+        // The original construction hashes 32 bytes and a domain alone 64 bytes. Any other combination starts with a
+        // 3-byte header telling which of the 32-byte slots follow, so its length is odd: no two combinations of
+        // version, domain and binding hash the same bytes.
+        let target_hash = match (self.version, &self.domain, binding) {
+            (DEFAULT_VERSION, None, None) => entropy.hash::<D>(),
+            (DEFAULT_VERSION, Some(domain_tag), None) => {
+                entropy.hash_with_prefix::<D>(&[domain_tag])
+            }
+            (version, domain, binding) => {
+                let header = [
+                    version,
+                    u8::from(domain.is_some()),
+                    binding.map_or(0, |(binding, _)| binding as u8),
+                ];
+                let domain: &[u8] = domain.as_ref().map_or(&[], |domain_tag| domain_tag);
+                let prefix: &[u8] = binding.map_or(&[], |(_, prefix)| prefix);
+                entropy.hash_with_prefix::<D>(&[&header, domain, prefix])
+            }
+        };
+
+        (DEFAULT_ROUNDS..self.rounds).fold(target_hash, |target_hash, _| {
+            Entropy::from(target_hash).hash::<D>()
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod pow_account {
+
+    use super::*;
+    use hex::FromHexError;
+
+    #[test]
+    fn new_entropy_has_a_length_of_32() {
+        let entropy = Entropy::new().entropy;
+        assert!(entropy.len().eq(&32))
+    }
+
+    #[test]
+    fn new_entropy_is_unique() {
+        let entropy_a = Entropy::new().entropy;
+        let entropy_b = Entropy::new().entropy;
+        assert_ne!(entropy_a, entropy_b)
+    }
+
+    #[test]
+    fn entropy_generates_256bit_hash() {
+        let hash = Entropy::new().hash::<Blake2s256>();
+        assert!(hash.len().eq(&32))
+    }
+
+    #[test]
+    fn entropy_created_from_a_set_of_bytes() {
+        let entropy_a = Entropy::new().entropy;
+        let entropy_b = Entropy::from(entropy_a);
+        assert_eq!(entropy_b.entropy, entropy_a)
+    }
+
+    #[test]
+    fn counter_salt_separates_repeated_entropy() {
+        let entropy = Entropy::from([0x42; 32]);
+        assert_ne!(
+            entropy.hash_with_suffix::<Blake2s256>(&0u64.to_be_bytes()),
+            entropy.hash_with_suffix::<Blake2s256>(&1u64.to_be_bytes())
+        )
+    }
+
+    #[test]
+    fn hash_found_with_or_without_counter_salt_passes_the_check() {
+        let mut hash_finder = HashFinder::new(3);
+        assert!(hash_finder.counter_salt());
+        assert!(hash_finder.check(hex::encode(hash_finder.find())).unwrap());
+
+        hash_finder.set_counter_salt(false);
+        assert!(hash_finder.check(hex::encode(hash_finder.find())).unwrap())
+    }
+
+    #[test]
+    fn blake2s_hash_can_be_validated() {
+        let origin_hash = "c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa";
+        let target_hash = "7478987293e1864fd833ae3607bc99b9b22e7ca39bced21c3b0428bd9c7218ba";
+
+        let origin_hash_vec = hex::decode(origin_hash).unwrap();
+        let origin_hash_bytes: [u8; 32] = origin_hash_vec.try_into().unwrap();
+
+        let entropy = Entropy::from(origin_hash_bytes);
+        let origin_hash_hex = hex::encode(entropy.hash::<Blake2s256>()).to_string();
+        assert_eq!(origin_hash_hex, target_hash)
+    }
+
+    #[test]
+    fn verify_agrees_with_the_check_of_a_hash_finder() {
+        let vectors = [
+            "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4",
+            "73b8f38be026335eb78946ea30434ff3cee4cff6544d49b4772f80397d40e72f",
+            "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e",
+            "c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa",
+        ];
+
+        for origin_hash in vectors {
+            let origin_hash_bytes = decode_origin_hash(origin_hash).unwrap();
+            for leading_zeros in 0..=6 {
+                assert_eq!(
+                    verify(&origin_hash_bytes, 4 * u32::from(leading_zeros)),
+                    HashFinder::new(leading_zeros).check(origin_hash).unwrap(),
+                    "{origin_hash} with {leading_zeros} leading zeros"
+                );
+            }
+        }
+
+        let origin_hash = decode_origin_hash(vectors[2]).unwrap();
+        assert!(verify(&origin_hash, 20));
+        assert!(!verify(&origin_hash, 21))
+    }
+
+    #[test]
+    fn same_target_compares_target_hashes() {
+        let origin_hash_a: [u8; 32] =
+            hex::decode("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let mut origin_hash_b = origin_hash_a;
+        origin_hash_b[31] ^= 0x01;
+
+        assert!(same_target(&origin_hash_a, &origin_hash_a));
+        assert!(!same_target(&origin_hash_a, &origin_hash_b))
+    }
+
+    #[test]
+    fn leading_zero_bits_are_counted_across_bytes() {
+        let mut hash = [0u8; 32];
+        assert_eq!(leading_zero_bits(&hash), 256);
+
+        hash[2] = 0x10;
+        assert_eq!(leading_zero_bits(&hash), 19);
+
+        hash[0] = 0x80;
+        assert_eq!(leading_zero_bits(&hash), 0)
+    }
+
+    #[test]
+    fn new_hash_prefix_has_non_zero_number_of_bits() {
+        let hash_prefix = HashPrefix::default();
+        assert!(hash_prefix.get() > 0)
+    }
+
+    #[test]
+    fn default_target_matches_max_value() {
+        let target = HashPrefix::default().target();
+        let max_target: [u8; 32] = [
+            0x00, 0x00, 0x0f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff,
+        ];
+
+        assert_eq!(target, max_target)
+    }
+
+    #[test]
+    fn target_is_only_recomputed_on_request() {
+        let mut hash_prefix = HashPrefix::default();
+        let default_target = hash_prefix.target();
+
+        hash_prefix.set_zero_bits(16);
+        assert_eq!(hash_prefix.get(), 16);
+        assert_eq!(hash_prefix.target(), default_target);
+
+        hash_prefix.recompute_target();
+        assert_eq!(hash_prefix.target(), HashPrefix::new(4).target())
+    }
+
+    #[test]
+    fn bit_level_target_masks_the_most_significant_bits() {
+        let target = HashPrefix::from_bits(9).target();
+        assert_eq!(&target[..3], &[0x00, 0x7f, 0xff]);
+
+        let target = HashPrefix::from_bits(16).target();
+        assert_eq!(&target[..3], &[0x00, 0x00, 0xff]);
+
+        assert_eq!(HashPrefix::from_bits(0).target(), [0xff; 32]);
+        let mut target = [0x00; 32];
+        target[31] = 0x01;
+        assert_eq!(HashPrefix::from_bits(255).target(), target);
+        assert_eq!(HashPrefix::from_bits(256).target(), target)
+    }
+
+    #[test]
+    fn target_decreases_across_the_whole_difficulty_range() {
+        let mut previous = HashPrefix::from_bits(0).target();
+
+        for bits in 1..=MAX_DIFFICULTY_BITS {
+            let target = HashPrefix::from_bits(bits).target();
+            assert!(target < previous, "target for {bits} bits is not harder");
+            assert_eq!(leading_zero_bits(&target), bits);
+
+            previous = target;
+        }
+    }
+
+    #[test]
+    fn targets_beyond_128_bits_accept_exactly_their_difficulty() {
+        for bits in [128, 129, 140, 200, 255] {
+            let hash_finder = HashFinder::with_bits(bits);
+            let target = hash_finder.target();
+            assert!(target[..16].iter().all(|&byte| byte == 0));
+
+            // The highest hash strictly below the target, and the lowest one with a leading zero bit fewer
+            let mut hardest_accepted = target;
+            hardest_accepted[31] &= 0xfe;
+            let mut easiest_rejected = [0u8; 32];
+            easiest_rejected[((bits - 1) / 8) as usize] = 0x80 >> ((bits - 1) % 8);
+
+            assert!(hash_finder.meets_target(&hardest_accepted), "{bits} bits");
+            assert!(!hash_finder.meets_target(&easiest_rejected), "{bits} bits");
+            assert!(leading_zero_bits(&hardest_accepted) >= bits);
+            assert_eq!(leading_zero_bits(&easiest_rejected), bits - 1);
+        }
+    }
+
+    #[test]
+    fn constant_time_comparison_agrees_with_byte_ordering() {
+        let target = HashPrefix::from_bits(4).target();
+
+        for _ in 0..1000 {
+            let hash = Entropy::new().entropy;
+            let other = Entropy::new().entropy;
+            assert_eq!(ct_lt(&hash, &other), hash < other);
+            assert_eq!(ct_lt(&hash, &target), hash < target);
+        }
+
+        let mut hash = [0x5a; 32];
+        assert!(!ct_lt(&hash, &hash));
+
+        let mut greater = hash;
+        greater[31] += 1;
+        assert!(ct_lt(&hash, &greater));
+        assert!(!ct_lt(&greater, &hash));
+
+        hash[0] = 0x00;
+        greater[0] = 0x01;
+        greater[1] = 0x00;
+        assert!(ct_lt(&hash, &greater));
+        assert!(!ct_lt(&greater, &hash))
+    }
+
+    #[test]
+    fn target_round_trips_through_from_target() {
+        let hash_finder = HashFinder::new(4);
+        let restored = HashFinder::from_target(hash_finder.target());
+        assert_eq!(restored, hash_finder);
+
+        for hash in [
+            "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e",
+            "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4",
+        ] {
+            assert_eq!(restored.check(hash), hash_finder.check(hash))
+        }
+        assert!(restored
+            .check("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e")
+            .unwrap());
+        assert!(!restored
+            .check("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4")
+            .unwrap())
+    }
+
+    #[test]
+    fn new_delegates_to_bit_level_difficulty() {
+        for leading_zeros in 0..=8 {
+            assert_eq!(
+                HashFinder::new(leading_zeros),
+                HashFinder::with_bits(4 * u32::from(leading_zeros))
+            )
+        }
+        assert_eq!(HashFinder::default(), HashFinder::with_bits(20))
+    }
+
+    #[test]
+    fn hash_passing_bit_level_difficulty_has_enough_zero_bits() {
+        let hash_finder = HashFinder::with_bits(9);
+        let leading_zero_bits: Vec<u32> = (0..32)
+            .map(|_| leading_zero_bits(&Entropy::from(hash_finder.find()).hash::<Blake2s256>()))
+            .collect();
+
+        assert!(leading_zero_bits.iter().all(|bits| *bits >= 9));
+        assert!(leading_zero_bits.iter().any(|bits| *bits < 12));
+
+        // The target hash starts with 17 zero bits
+        let hash = String::from("73b8f38be026335eb78946ea30434ff3cee4cff6544d49b4772f80397d40e72f");
+        assert!(HashFinder::with_bits(17).check(hash.clone()).unwrap());
+        assert!(!HashFinder::with_bits(18).check(hash).unwrap())
+    }
+
+    #[test]
+    fn zero_bits_accept_any_hash() {
+        let hash = String::from("c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa");
+        assert!(HashFinder::with_bits(0).check(hash).unwrap());
+        assert!(HashFinder::with_bits(0)
+            .check(hex::encode(Entropy::new().entropy))
+            .unwrap())
+    }
+
+    #[test]
+    fn can_find_a_hash_which_starts_from_a_specific_pattern() {
+        let origin_hash = HashFinder::new(4).find();
+        let target_hash = Entropy::from(origin_hash).hash::<Blake2s256>();
 
         let hash_hex = hex::encode(target_hash);
         assert!(hash_hex.starts_with("0000"))
     }
 
+    #[test]
+    fn find_reports_the_number_of_attempts() {
+        let hash_finder = HashFinder::new(2);
+        let (origin_hash, attempts) = hash_finder.find_with_attempts();
+        assert!(attempts >= 1);
+        assert!(hash_finder.check(hex::encode(origin_hash)).unwrap());
+
+        // Every candidate is accepted, so the first attempt succeeds
+        assert_eq!(HashFinder::with_bits(0).find_with_attempts().1, 1)
+    }
+
+    #[test]
+    fn bounded_search_gives_up_after_the_limit() {
+        assert_eq!(HashFinder::with_bits(0).find_with_limit(0), None);
+        assert_eq!(HashFinder::new(16).find_with_limit(1000), None);
+
+        let origin_hash = HashFinder::with_bits(0).find_with_limit(1).unwrap();
+        assert!(HashFinder::with_bits(0)
+            .check(hex::encode(origin_hash))
+            .unwrap())
+    }
+
+    #[test]
+    fn search_gives_up_after_the_timeout() {
+        let started_at = Instant::now();
+        assert_eq!(
+            HashFinder::new(8).find_with_timeout(Duration::from_millis(1)),
+            None
+        );
+        assert!(started_at.elapsed() < Duration::from_secs(1));
+
+        let origin_hash = HashFinder::new(1)
+            .find_with_timeout(Duration::from_secs(60))
+            .unwrap();
+        assert!(HashFinder::new(1).check(hex::encode(origin_hash)).unwrap())
+    }
+
+    #[test]
+    fn progress_is_reported_every_interval() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut reports = Vec::new();
+        let result = HashFinder::new(64).find_bounded(
+            &mut ChaCha20Rng::seed_from_u64(0),
+            &mut SaltCounter::reserve(),
+            2 * PROGRESS_INTERVAL + 1,
+            &mut |attempts| reports.push(attempts),
+        );
+
+        assert_eq!(result, None);
+        assert_eq!(reports, vec![PROGRESS_INTERVAL, 2 * PROGRESS_INTERVAL]);
+    }
+
+    #[test]
+    fn progress_search_finds_a_valid_hash() {
+        let mut reports = 0;
+        let origin_hash = HashFinder::new(2).find_with_progress(|_| reports += 1);
+
+        assert!(HashFinder::new(2).check_bytes(&origin_hash));
+        assert!(reports <= 1)
+    }
+
+    #[test]
+    fn proof_is_only_valid_for_its_number_of_rounds() {
+        let three_rounds = HashFinder::new(2).with_rounds(3);
+        let origin_hash = three_rounds.find();
+
+        assert!(three_rounds.check_bytes(&origin_hash));
+
+        // The target hash is 00003f8d… with 3 rounds and 11b45ed9… with the default 2 rounds
+        let origin_hash = "af89267a897e5c89ecf6e55ce7f0e8454e16daafa20eb788e1f9e710a5a894af";
+        assert!(HashFinder::new(4)
+            .with_rounds(3)
+            .check(origin_hash)
+            .unwrap());
+        assert!(!HashFinder::new(1).check(origin_hash).unwrap());
+        assert_eq!(HashFinder::new(3).with_rounds(1), HashFinder::new(3));
+    }
+
+    #[test]
+    fn proofs_of_distinct_domains_reject_each_other() {
+        let service_a = HashFinder::new(2).with_domain(b"service-a");
+        let service_b = HashFinder::new(2).with_domain(b"service-b");
+
+        // The target hash is 0059bbf5… for service-a, f9da2434… for service-b and 857482d1… without a domain
+        let origin_hash = "0e1f27c00716ec17d8307e2c85eca84bd1ed34dfead1159dde83ab7dc4b0cf88";
+        assert!(service_a.check(origin_hash).unwrap());
+        assert!(!HashFinder::new(1)
+            .with_domain(b"service-b")
+            .check(origin_hash)
+            .unwrap());
+        assert!(!HashFinder::new(1).check(origin_hash).unwrap());
+
+        let origin_hash = service_b.find();
+        assert!(service_b.check_bytes(&origin_hash));
+        assert_eq!(service_b, HashFinder::new(2).with_domain(b"service-b"))
+    }
+
+    #[test]
+    fn hex_search_returns_a_lowercase_hash_accepted_by_check() {
+        let hash_finder = HashFinder::new(2).with_rounds(3);
+        let origin_hash = hash_finder.find_hex();
+
+        assert_eq!(origin_hash.len(), 64);
+        assert!(origin_hash
+            .bytes()
+            .all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')));
+        assert!(hash_finder.check(&origin_hash).unwrap())
+    }
+
+    #[test]
+    fn harder_hash_finders_are_greater() {
+        assert!(HashFinder::new(8) > HashFinder::new(4));
+        assert!(HashFinder::with_bits(13) > HashFinder::with_bits(12));
+        assert!(HashFinder::new(0) < HashFinder::default());
+
+        let mut hash_finders = vec![HashFinder::new(5), HashFinder::new(1), HashFinder::new(3)];
+        hash_finders.sort();
+        assert_eq!(
+            hash_finders,
+            vec![HashFinder::new(1), HashFinder::new(3), HashFinder::new(5)]
+        );
+        assert_eq!(
+            hash_finders.iter().max().map(HashFinder::difficulty_bits),
+            Some(20)
+        );
+
+        let hash_finder = HashFinder::with_version(4, 2);
+        assert_ne!(
+            hash_finder.cmp(&HashFinder::new(4)),
+            core::cmp::Ordering::Equal
+        );
+        assert!(hash_finder < HashFinder::new(5) && hash_finder > HashFinder::new(3))
+    }
+
+    #[test]
+    fn counter_search_finds_hashes_that_pass_the_check() {
+        for hash_finder in [
+            HashFinder::new(3),
+            HashFinder::with_version(2, 2),
+            HashFinder::new(2).with_rounds(3).with_domain(b"service-a"),
+        ] {
+            let origin_hash = hash_finder.find_counter();
+            assert!(hash_finder.check(hex::encode(origin_hash)).unwrap());
+        }
+
+        assert_ne!(
+            HashFinder::new(0).find_counter(),
+            HashFinder::new(0).find_counter()
+        )
+    }
+
+    #[test]
+    fn string_checks_accept_borrowed_hashes() {
+        let hash_finder = HashFinder::new(3);
+        let payload_hash = payload_prehash(b"account-request");
+        let origin_hash = hex::encode(hash_finder.find_with_prehash(&payload_hash));
+
+        assert!(hash_finder
+            .check_with_prehash(&payload_hash, origin_hash.as_str())
+            .unwrap());
+        assert!(hash_finder
+            .check_with_prehash(&payload_hash, origin_hash.as_bytes())
+            .unwrap());
+        assert!(hash_finder
+            .check_with_prehash(&payload_hash, origin_hash)
+            .unwrap());
+
+        let origin_hash = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+        assert!(hash_finder.check_band(origin_hash).unwrap())
+    }
+
+    #[test]
+    fn cancelled_search_returns_nothing() {
+        let cancel = AtomicBool::new(true);
+        assert_eq!(HashFinder::new(8).find_cancellable(&cancel), None);
+
+        let cancel = AtomicBool::new(false);
+        let origin_hash = HashFinder::new(1).find_cancellable(&cancel).unwrap();
+        assert!(HashFinder::new(1).check(hex::encode(origin_hash)).unwrap())
+    }
+
+    #[test]
+    fn shared_flag_cancels_running_searches() {
+        use std::sync::Arc;
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let searches: Vec<_> = (0..2)
+            .map(|_| {
+                let cancel = Arc::clone(&cancel);
+                std::thread::spawn(move || HashFinder::new(8).find_cancellable(&cancel))
+            })
+            .collect();
+
+        std::thread::sleep(Duration::from_millis(10));
+        cancel.store(true, Ordering::Relaxed);
+
+        for search in searches {
+            assert_eq!(search.join().unwrap(), None)
+        }
+    }
+
+    #[test]
+    fn prehash_bound_hash_is_only_valid_for_its_payload() {
+        let payload_hash = payload_prehash(b"payload-a");
+        let other_payload_hash = payload_prehash(b"payload-b");
+
+        let origin_hash = HashFinder::new(4).find_with_prehash(&payload_hash);
+
+        assert!(HashFinder::new(4)
+            .check_with_prehash(&payload_hash, hex::encode(origin_hash))
+            .unwrap());
+        assert!(!HashFinder::new(4)
+            .check_with_prehash(&other_payload_hash, hex::encode(origin_hash))
+            .unwrap())
+    }
+
+    #[test]
+    fn dual_domain_hash_is_valid_under_both_domains_only() {
+        let hash_finder = HashFinder::new(2);
+        let origin_hash = hash_finder.find_dual_domain(b"service-a", b"service-b");
+
+        assert!(hash_finder
+            .with_domain(b"service-a")
+            .check_bytes(&origin_hash));
+        assert!(hash_finder
+            .with_domain(b"service-b")
+            .check_bytes(&origin_hash));
+        assert!(hash_finder
+            .check_dual_domain(b"service-b", b"service-a", hex::encode(origin_hash))
+            .unwrap());
+
+        let hash = String::from("c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa");
+        assert!(!HashFinder::new(1)
+            .check_dual_domain(b"service-a", b"service-b", hash)
+            .unwrap())
+    }
+
+    #[test]
+    fn domain_challenge_prehash_and_context_proofs_are_distinct() {
+        // The target hash is 00004021… for the domain service-a, and f58d863a…, f02ad65f…, 39368462… and 57dd815f…
+        // for the same 32 bytes as a challenge, a payload digest, the digest of a context and for no binding at all
+        let origin_hash = "5cca42a9b025fab43a9997180479319b7c08a6242405b3a96e9bf4b2706314bf";
+        let domain_tag = domain_tag::<Blake2s256>(b"service-a");
+        let context = [DOMAIN_TAG_PREFIX, b"service-a"].concat();
+
+        assert!(HashFinder::new(4)
+            .with_domain(b"service-a")
+            .check(origin_hash)
+            .unwrap());
+        assert!(!HashFinder::new(1)
+            .check_with_challenge(&Challenge::from_bytes(domain_tag), origin_hash)
+            .unwrap());
+        assert!(!HashFinder::new(1)
+            .check_with_prehash(&domain_tag, origin_hash)
+            .unwrap());
+        assert!(!HashFinder::new(1).check_for(&context, origin_hash).unwrap());
+        assert!(!HashFinder::new(1).check(origin_hash).unwrap())
+    }
+
+    #[test]
+    fn settings_and_bindings_never_hash_the_same_bytes() {
+        let origin_hash = [0x5a; 32];
+        let prefix = domain_tag::<Blake2s256>(b"service-a");
+        let plain = HashFinder::new(4);
+        let versioned = HashFinder::with_version(4, 2);
+        let bound = |hash_finder: HashFinder, binding| {
+            hash_finder.bound_target_hash(binding, &prefix, origin_hash)
+        };
+
+        let target_hashes = [
+            plain.target_hash(origin_hash),
+            plain.with_domain(b"service-a").target_hash(origin_hash),
+            versioned.target_hash(origin_hash),
+            versioned.with_domain(b"service-a").target_hash(origin_hash),
+            bound(plain, Binding::Challenge),
+            bound(plain, Binding::Prehash),
+            bound(plain, Binding::Context),
+            bound(plain, Binding::Records),
+            bound(versioned, Binding::Challenge),
+            bound(plain.with_domain(b"service-a"), Binding::Challenge),
+            bound(plain.with_domain(b"service-b"), Binding::Challenge),
+        ];
+        for (index, target_hash) in target_hashes.iter().enumerate() {
+            for (other_index, other) in target_hashes.iter().enumerate().skip(index + 1) {
+                assert_ne!(target_hash, other, "{index} and {other_index}")
+            }
+        }
+    }
+
+    #[test]
+    fn band_bounds_are_validated() {
+        let mut lo = [0u8; 32];
+        lo[0] = 0x70;
+        assert!(HashFinder::with_band(lo, lo).is_none());
+        assert!(HashFinder::with_band([0xff; 32], lo).is_none())
+    }
+
+    #[test]
+    fn target_hash_must_fall_within_the_band() {
+        // The target hash starts with 0x74
+        let hash = String::from("c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa");
+
+        let [mut lo, mut hi] = [[0u8; 32]; 2];
+        lo[0] = 0x70;
+        hi[0] = 0x78;
+        let hash_finder = HashFinder::with_band(lo, hi).unwrap();
+        assert!(hash_finder.check_band(hash.clone()).unwrap());
+
+        lo[0] = 0x75;
+        let hash_finder = HashFinder::with_band(lo, hi).unwrap();
+        assert!(!hash_finder.check_band(hash.clone()).unwrap());
+        assert!(hash_finder.check(hash).unwrap());
+
+        let origin_hash = hash_finder.find_band();
+        let target_hash = Entropy::from(origin_hash).hash::<Blake2s256>();
+        assert!(lo <= target_hash && target_hash < hi)
+    }
+
+    #[test]
+    fn paced_search_finds_a_valid_hash() {
+        let hash_finder = HashFinder::new(2);
+
+        for attempts_per_burst in [0, 16] {
+            let origin_hash = hash_finder.find_paced(attempts_per_burst, Duration::from_micros(10));
+            assert!(hash_finder.check(hex::encode(origin_hash)).unwrap())
+        }
+    }
+
+    #[test]
+    fn buffered_search_finds_a_valid_hash() {
+        let hash_finder = HashFinder::new(3);
+
+        for buffer_bytes in [0, 33, 4096] {
+            let origin_hash = hash_finder.find_buffered(buffer_bytes);
+            assert!(hash_finder.check(hex::encode(origin_hash)).unwrap())
+        }
+    }
+
+    #[test]
+    fn found_hashes_are_sent_to_a_bounded_channel() {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        let producer = std::thread::spawn(move || HashFinder::new(2).find_to_sync_channel(tx, 4));
+
+        let origin_hashes: Vec<[u8; 32]> = rx.iter().collect();
+        assert_eq!(producer.join().unwrap(), 4);
+        assert_eq!(origin_hashes.len(), 4);
+        for origin_hash in origin_hashes {
+            assert!(HashFinder::new(2).check(hex::encode(origin_hash)).unwrap())
+        }
+    }
+
+    #[test]
+    fn disconnected_receiver_stops_the_search() {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        drop(rx);
+
+        assert_eq!(HashFinder::new(1).find_to_sync_channel(tx, 4), 0)
+    }
+
+    #[test]
+    fn iterator_yields_independent_valid_hashes() {
+        let hash_finder = HashFinder::new(2);
+        let origin_hashes =
+            std::thread::spawn(move || hash_finder.iter().take(4).collect::<Vec<[u8; 32]>>())
+                .join()
+                .unwrap();
+
+        assert_eq!(origin_hashes.len(), 4);
+        for (index, origin_hash) in origin_hashes.iter().enumerate() {
+            assert!(hash_finder.check(hex::encode(origin_hash)).unwrap());
+            assert!(!origin_hashes[..index].contains(origin_hash))
+        }
+    }
+
+    #[test]
+    fn ranked_batch_is_sorted_by_achieved_work() {
+        let hash_finder = HashFinder::new(2);
+        let ranked = hash_finder.find_batch_ranked(8);
+
+        assert_eq!(ranked.len(), 8);
+        assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        for (origin_hash, leading_zeros) in ranked {
+            assert!(leading_zeros >= 2);
+            assert_eq!(hash_finder.check_difficulty(&origin_hash), leading_zeros);
+            assert!(hash_finder.check(hex::encode(origin_hash)).unwrap())
+        }
+    }
+
+    #[test]
+    fn round_robin_returns_a_solved_difficulty() {
+        let difficulties = [4, 2, 3];
+        let (leading_zeros, origin_hash) = HashFinder::default().find_round_robin(&difficulties);
+
+        assert!(difficulties.contains(&leading_zeros));
+        assert!(HashFinder::new(leading_zeros)
+            .check(hex::encode(origin_hash))
+            .unwrap())
+    }
+
+    #[test]
+    fn round_robin_keeps_the_settings_of_the_finder() {
+        let hash_finder = HashFinder::with_bits(4)
+            .with_rounds(3)
+            .with_domain(b"round-robin");
+        let (leading_zeros, origin_hash) = hash_finder.find_round_robin(&[3, 2]);
+
+        let tier = HashFinder::new(leading_zeros)
+            .with_rounds(3)
+            .with_domain(b"round-robin");
+        assert!(tier.check(hex::encode(origin_hash)).unwrap());
+        assert!(hash_finder.check_difficulty(&origin_hash) >= u32::from(leading_zeros))
+    }
+
+    #[test]
+    fn expected_attempts_grow_with_the_number_of_leading_zeros() {
+        assert_eq!(HashFinder::new(1).expected_attempts().round(), 16.0);
+        assert_eq!(HashFinder::default().expected_attempts().round(), 1048576.0);
+        assert!(HashFinder::new(6).expected_attempts() > HashFinder::new(5).expected_attempts())
+    }
+
+    #[test]
+    fn expected_attempts_follow_bit_level_difficulty() {
+        for bits in [1, 7, 18, 30] {
+            let expected_attempts = HashFinder::with_bits(bits).expected_attempts();
+            assert_eq!(expected_attempts.round(), 2f64.powi(bits as i32))
+        }
+    }
+
+    #[test]
+    fn estimated_duration_divides_expected_attempts_by_the_hash_rate() {
+        let duration = HashFinder::with_bits(20).estimate_duration(1048576.0);
+        assert!((duration.as_secs_f64() - 1.0).abs() < 1e-3);
+
+        assert_eq!(HashFinder::new(64).estimate_duration(1e9), Duration::MAX);
+        assert_eq!(HashFinder::new(4).estimate_duration(0.0), Duration::MAX);
+        assert_eq!(HashFinder::new(4).estimate_duration(-1.0), Duration::MAX)
+    }
+
+    #[test]
+    fn accept_probability_is_computed_from_the_target() {
+        let hash_finder = HashFinder {
+            target: [
+                0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00,
+            ],
+            ..HashFinder::default()
+        };
+        assert_eq!(hash_finder.accept_probability(), 0.5);
+
+        let hash_finder = HashFinder::new(5);
+        assert_eq!(
+            hash_finder.accept_probability() * hash_finder.expected_attempts(),
+            1.0
+        )
+    }
+
+    #[test]
+    fn resumed_batch_continues_where_it_stopped() {
+        let hash_finder = HashFinder::new(2);
+        let base = Entropy::new().entropy;
+
+        let (full_batch, full_next_index) = hash_finder.find_batch_resumable(base, 0, 3);
+
+        let (mut resumed_batch, next_index) = hash_finder.find_batch_resumable(base, 0, 1);
+        let (rest, resumed_next_index) = hash_finder.find_batch_resumable(base, next_index, 2);
+        resumed_batch.extend(rest);
+
+        assert_eq!(resumed_batch, full_batch);
+        assert_eq!(resumed_next_index, full_next_index);
+        for origin_hash in full_batch {
+            assert!(hash_finder.check(hex::encode(origin_hash)).unwrap())
+        }
+    }
+
+    #[test]
+    fn hash_of_one_version_fails_the_check_of_another() {
+        let v2 = HashFinder::with_version(4, 2);
+        let origin_hash = v2.find();
+
+        assert!(v2.check(hex::encode(origin_hash)).unwrap());
+        assert!(!HashFinder::with_version(4, 3)
+            .check(hex::encode(origin_hash))
+            .unwrap());
+
+        // This origin hash passes the default version at 3 leading zeros
+        let hash = String::from("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
+        assert!(HashFinder::with_version(3, DEFAULT_VERSION)
+            .check(hash.clone())
+            .unwrap());
+        assert!(!HashFinder::with_version(3, 2).check(hash).unwrap())
+    }
+
+    #[test]
+    fn hash_of_one_digest_fails_the_check_of_another() {
+        let sha256 = HashFinder::new(4).with_digest::<sha2::Sha256>();
+
+        // The SHA-256 target hash is 000036eebf414ad54d181d1d40a234e49739d9ad7a016aeb34e7ac5f5f9fcc2a
+        // and the Blake2s target hash is e142f0b72ef8f7afa71d011d40a95dc1c699cf9fbe92b7f3f0ceeb4304546ed0
+        let hash = String::from("271740448e5353d798b8ebf21559e05a5544604f770b3e67ec1fb1dee34f46c0");
+        assert!(sha256.check(hash.clone()).unwrap());
+        assert!(!HashFinder::new(1).check(hash).unwrap());
+
+        // The Blake2s target hash is 00000c0fd80d3af2a3c41459ebcd9a73adbacd8c442b229e75afad56c2c08f2c
+        // and the SHA-256 target hash is 679c648933b2419161311c61ac0b716c856092041993a11efabf79bd932fbd4b
+        let hash = String::from("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e");
+        assert!(HashFinder::new(5).check(hash.clone()).unwrap());
+        assert!(!HashFinder::new(1)
+            .with_digest::<sha2::Sha256>()
+            .check(hash)
+            .unwrap())
+    }
+
+    #[test]
+    fn hash_found_with_a_digest_passes_its_check() {
+        let sha256 = HashFinder::new(3).with_digest::<sha2::Sha256>();
+        let origin_hash = sha256.find();
+
+        assert!(sha256.check(hex::encode(origin_hash)).unwrap());
+        assert_eq!(sha256.with_digest::<Blake2s256>(), HashFinder::new(3))
+    }
+
+    #[test]
+    fn identically_seeded_searches_find_the_same_hash() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        for counter_salt in [true, false] {
+            let mut hash_finder = HashFinder::new(3);
+            hash_finder.set_counter_salt(counter_salt);
+
+            let origin_hash_a = hash_finder.find_with_rng(&mut ChaCha20Rng::seed_from_u64(42));
+            // Another search in between doesn't move the counter of the next one
+            hash_finder.find();
+            let origin_hash_b = hash_finder.find_with_rng(&mut ChaCha20Rng::seed_from_u64(42));
+            let origin_hash_c = hash_finder.find_with_rng(&mut ChaCha20Rng::seed_from_u64(43));
+
+            assert_eq!(origin_hash_a, origin_hash_b, "{counter_salt}");
+            assert_ne!(origin_hash_a, origin_hash_c, "{counter_salt}");
+            assert!(hash_finder.check(hex::encode(origin_hash_a)).unwrap())
+        }
+    }
+
+    #[test]
+    fn check_accepts_borrowed_and_decoded_hashes() {
+        let buffer = "origin=3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4;";
+        let hash = &buffer[7..71];
+
+        assert!(HashFinder::new(3).check(hash).unwrap());
+        assert!(HashFinder::new(3).check(hash.as_bytes()).unwrap());
+        assert!(!HashFinder::new(4).check(hash).unwrap());
+
+        let origin_hash = decode_origin_hash(hash).unwrap();
+        assert!(HashFinder::new(3).check_bytes(&origin_hash));
+        assert!(!HashFinder::new(4).check_bytes(&origin_hash));
+
+        let err = HashFinder::new(3).check(&buffer[6..70]).unwrap_err();
+        assert_eq!(
+            err,
+            PowError::InvalidHex(FromHexError::InvalidHexCharacter { c: '=', index: 0 })
+        )
+    }
+
     #[test]
     fn checks_the_hash_for_the_required_number_of_leading_zeros() {
         let hash = String::from("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
@@ -291,19 +2792,34 @@ mod pow_account {
         assert!(HashFinder::default().check(hash).unwrap());
 
         let hash = String::from("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
-        assert_eq!(HashFinder::new(4).check(hash).unwrap(), false);
+        assert!(!HashFinder::new(4).check(hash).unwrap());
 
         let hash = String::from("3c+727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4");
         let err = HashFinder::new(4).check(hash).unwrap_err();
-        assert_eq!(err, FromHexError::InvalidHexCharacter { c: '+', index: 2 });
+        assert_eq!(
+            err,
+            PowError::InvalidHex(FromHexError::InvalidHexCharacter { c: '+', index: 2 })
+        );
 
         let hash = String::from("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d");
         let err = HashFinder::new(4).check(hash).unwrap_err();
-        assert_eq!(err, FromHexError::OddLength);
+        assert_eq!(
+            err,
+            PowError::InvalidLength {
+                len: 63,
+                expected: 64
+            }
+        );
 
         let hash =
             String::from("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d444d4");
         let err = HashFinder::new(4).check(hash).unwrap_err();
-        assert_eq!(err, FromHexError::InvalidStringLength)
+        assert_eq!(
+            err,
+            PowError::InvalidLength {
+                len: 68,
+                expected: 64
+            }
+        )
     }
 }
@@ -0,0 +1,260 @@
+//! Memory-hard proofs whose second round is Argon2id, enabled with the `argon2` feature.
+//!
+//! Every attempt of a Blake2s search costs a few hundred nanoseconds and almost no memory, which GPUs and ASICs
+//! parallelize far better than browsers. With [`MemoryHardFinder`] the target hash is computed with Argon2id, so
+//! every attempt needs the configured amount of memory and specialized hardware gains little over a CPU. Verification
+//! costs one Argon2id evaluation, so the memory cost should stay well below what the server can afford per request.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+
+use crate::{decode_origin_hash, Finder, HashFinder, PowError, SaltCounter};
+
+/// Salt of the Argon2id round, constant so that the target hash only depends on the origin hash.
+const ARGON2_SALT: &[u8] = b"pow-account-argon2id";
+
+/// Memory cost of a [`MemoryHardFinder`] in KiB when none is configured.
+pub const DEFAULT_MEMORY_COST: u32 = 4096;
+
+/// Number of Argon2id passes of a [`MemoryHardFinder`] when none is configured.
+pub const DEFAULT_ITERATIONS: u32 = 1;
+
+/// `MemoryHardFinder` finds and checks origin hashes like [`HashFinder`], with Argon2id as the second round.
+///
+/// Origin hashes are drawn as with [`HashFinder::find`], and the target hash is the 32-byte Argon2id digest of the
+/// origin hash. An origin hash found by one MemoryHardFinder only passes the checks of a MemoryHardFinder with the
+/// same difficulty, memory cost and number of iterations.
+///
+/// Every attempt costs a full Argon2id evaluation, so the difficulties are much lower than those of a HashFinder:
+/// each bit still doubles the expected number of attempts.
+///
+/// # Example
+/// ```
+/// use pow_account::MemoryHardFinder;
+///
+/// let finder = MemoryHardFinder::with_bits(2).with_cost(256, 1).unwrap();
+/// let origin_hash = finder.find();
+///
+/// assert!(finder.check(hex::encode(origin_hash)).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemoryHardFinder {
+    hash_finder: HashFinder,
+    params: Params,
+}
+
+impl MemoryHardFinder {
+    /// Returns a MemoryHardFinder with a specified number of target leading zeros and the default costs
+    ///
+    /// Each leading zero is a hexadecimal digit of 4 bits, as with [`HashFinder::new`].
+    pub fn new(leading_zeros: u8) -> Self {
+        MemoryHardFinder::from_hash_finder(HashFinder::new(leading_zeros))
+    }
+
+    /// Returns a MemoryHardFinder with a specified number of target leading zero bits and the default costs
+    pub fn with_bits(bits: u32) -> Self {
+        MemoryHardFinder::from_hash_finder(HashFinder::with_bits(bits))
+    }
+
+    fn from_hash_finder(hash_finder: HashFinder) -> Self {
+        let params = Params::new(DEFAULT_MEMORY_COST, DEFAULT_ITERATIONS, 1, Some(32))
+            .expect("the default costs are valid Argon2 parameters");
+        MemoryHardFinder {
+            hash_finder,
+            params,
+        }
+    }
+
+    /// Returns the MemoryHardFinder with another memory cost and number of iterations
+    ///
+    /// # Parameters
+    ///
+    /// - `memory_cost`: The memory used by every attempt, in KiB. At least 8 KiB are required.
+    /// - `iterations`: The number of Argon2id passes over the memory. At least 1 is required.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::InvalidCost`] if Argon2 rejects the costs.
+    pub fn with_cost(self, memory_cost: u32, iterations: u32) -> Result<Self, PowError> {
+        let params =
+            Params::new(memory_cost, iterations, 1, Some(32)).map_err(|_| PowError::InvalidCost)?;
+        Ok(MemoryHardFinder { params, ..self })
+    }
+
+    /// Returns the memory cost of every attempt in KiB
+    pub fn memory_cost(&self) -> u32 {
+        self.params.m_cost()
+    }
+
+    /// Returns the number of Argon2id passes of every attempt
+    pub fn iterations(&self) -> u32 {
+        self.params.t_cost()
+    }
+
+    /// Finds an origin hash whose Argon2id digest starts with the required leading zeros
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    pub fn find(&self) -> [u8; 32] {
+        let mut memory = self.memory();
+        let mut counter = SaltCounter::reserve();
+        loop {
+            let origin_hash = self.hash_finder.origin_candidate(&mut counter);
+            if self.meets_target(&origin_hash, &mut memory) {
+                return origin_hash;
+            }
+        }
+    }
+
+    /// Finds an origin hash, giving up after `max_attempts` attempts
+    ///
+    /// This bounds the time spent on the calling thread, as [`HashFinder::find_with_limit`] does.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Some` origin hash, or `None` if none was found within `max_attempts` attempts.
+    pub fn find_with_limit(&self, max_attempts: u64) -> Option<[u8; 32]> {
+        let mut memory = self.memory();
+        let mut counter = SaltCounter::reserve();
+        (0..max_attempts)
+            .map(|_| self.hash_finder.origin_candidate(&mut counter))
+            .find(|origin_hash| self.meets_target(origin_hash, &mut memory))
+    }
+
+    /// Determines whether a given origin hash produces an Argon2id digest with the required leading zeros
+    ///
+    /// # Parameters
+    ///
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation.
+    pub fn check<S: AsRef<[u8]>>(&self, origin_hash: S) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+
+        Ok(self.check_bytes(&origin_hash_bytes))
+    }
+
+    /// Determines whether a decoded origin hash produces an Argon2id digest with the required leading zeros
+    pub fn check_bytes(&self, origin_hash: &[u8; 32]) -> bool {
+        self.meets_target(origin_hash, &mut self.memory())
+    }
+
+    /// Returns the Argon2id digest of an origin hash, which is compared to the target
+    pub fn target_hash(&self, origin_hash: &[u8; 32]) -> [u8; 32] {
+        self.argon2_hash(origin_hash, &mut self.memory())
+    }
+
+    fn memory(&self) -> Vec<argon2::Block> {
+        vec![argon2::Block::default(); self.params.block_count()]
+    }
+
+    fn argon2_hash(&self, origin_hash: &[u8; 32], memory: &mut [argon2::Block]) -> [u8; 32] {
+        let mut target_hash = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+            .hash_password_into_with_memory(origin_hash, ARGON2_SALT, &mut target_hash, memory)
+            .expect("the salt, output and memory sizes are valid");
+        target_hash
+    }
+
+    fn meets_target(&self, origin_hash: &[u8; 32], memory: &mut [argon2::Block]) -> bool {
+        self.hash_finder
+            .meets_target(&self.argon2_hash(origin_hash, memory))
+    }
+}
+
+/// The proof is the origin hash of [`MemoryHardFinder::find`], verified with [`MemoryHardFinder::check_bytes`].
+impl Finder for MemoryHardFinder {
+    type Proof = [u8; 32];
+
+    fn find_proof(&self) -> Self::Proof {
+        self.find()
+    }
+
+    fn verify_proof(&self, proof: &Self::Proof) -> bool {
+        self.check_bytes(proof)
+    }
+}
+
+impl PartialEq for MemoryHardFinder {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash_finder == other.hash_finder
+            && self.memory_cost() == other.memory_cost()
+            && self.iterations() == other.iterations()
+    }
+}
+
+impl Eq for MemoryHardFinder {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn finder(bits: u32) -> MemoryHardFinder {
+        MemoryHardFinder::with_bits(bits).with_cost(64, 1).unwrap()
+    }
+
+    #[test]
+    fn found_hash_passes_the_check() {
+        let finder = finder(3);
+        let origin_hash = finder.find();
+
+        assert!(finder.check_bytes(&origin_hash));
+        assert!(finder.check(hex::encode(origin_hash)).unwrap());
+        assert!(finder.target_hash(&origin_hash)[0] < 0x20)
+    }
+
+    #[test]
+    fn proof_depends_on_the_costs() {
+        let origin_hash = finder(0).find();
+        let target_hash = finder(0).target_hash(&origin_hash);
+
+        assert_ne!(
+            MemoryHardFinder::with_bits(0)
+                .with_cost(128, 1)
+                .unwrap()
+                .target_hash(&origin_hash),
+            target_hash
+        );
+        assert_ne!(
+            MemoryHardFinder::with_bits(0)
+                .with_cost(64, 2)
+                .unwrap()
+                .target_hash(&origin_hash),
+            target_hash
+        );
+        assert_ne!(target_hash, HashFinder::default().target_hash(origin_hash))
+    }
+
+    #[test]
+    fn costs_are_validated() {
+        let finder = MemoryHardFinder::new(1);
+        assert_eq!(finder.memory_cost(), DEFAULT_MEMORY_COST);
+        assert_eq!(finder.iterations(), DEFAULT_ITERATIONS);
+
+        assert_eq!(finder.clone().with_cost(4, 1), Err(PowError::InvalidCost));
+        assert_eq!(finder.with_cost(64, 0), Err(PowError::InvalidCost))
+    }
+
+    #[test]
+    fn bounded_search_gives_up() {
+        assert_eq!(finder(64).find_with_limit(3), None);
+        assert!(finder(0).find_with_limit(1).is_some());
+        assert!(finder(1).check("abcd").is_err())
+    }
+
+    #[test]
+    fn finder_trait_uses_the_argon2id_round() {
+        let finder = finder(2);
+        let origin_hash = Finder::find_proof(&finder);
+
+        assert!(finder.verify_proof(&origin_hash));
+        assert_eq!(
+            finder.verify_proof(&origin_hash),
+            finder.check_bytes(&origin_hash)
+        )
+    }
+}
@@ -0,0 +1,755 @@
+//! Proof-of-work gates for HTTP services, as a `tower` layer and an `axum` extractor.
+//!
+//! A client proves its work with two request headers: [`CHALLENGE_HEADER`] holds the hexadecimal [`Challenge`] and
+//! [`SOLUTION_HEADER`] holds the hexadecimal origin hash found for it with [`HashFinder::find_with_challenge`]. A
+//! request whose headers are missing, malformed or don't meet the difficulty is rejected with
+//! `429 Too Many Requests`, a fresh challenge in [`CHALLENGE_HEADER`] and the difficulty in [`DIFFICULTY_HEADER`],
+//! so the client can solve it and retry.
+//!
+//! Both gates verify proofs with a [`PowGate`]. Its challenges are signed with a [`ChallengeKey`] and expire, so a
+//! client can't make up a challenge or solve one in advance, and every accepted proof is recorded in a
+//! [`SolutionStore`] until its challenge expires, so the same headers are only accepted once.
+//!
+//! The signature and the proof are checked on the thread handling the request, while the store is written on the
+//! blocking thread pool of the Tokio runtime, so a slow store such as a distant Redis server doesn't stall the other
+//! tasks of the runtime. Both gates must therefore run within a Tokio runtime.
+
+#[cfg(feature = "tower")]
+use core::future::Future;
+#[cfg(feature = "axum")]
+use core::marker::PhantomData;
+#[cfg(feature = "tower")]
+use core::pin::Pin;
+#[cfg(feature = "tower")]
+use core::task::{Context, Poll};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2s256, Digest};
+use http::{HeaderMap, HeaderValue, Response, StatusCode};
+
+use tokio::task::{JoinError, JoinHandle};
+
+use crate::{decode_origin_hash, Challenge, ChallengeKey, HashFinder, PowError, SolutionStore};
+
+/// Name of the request header with the hexadecimal challenge the solution was found for.
+pub const CHALLENGE_HEADER: &str = "x-pow-challenge";
+
+/// Name of the request header with the hexadecimal origin hash.
+pub const SOLUTION_HEADER: &str = "x-pow-solution";
+
+/// Name of the response header with the difficulty a rejected request must meet, as written by the
+/// [`Display`](core::fmt::Display) implementation of [`HashFinder`].
+pub const DIFFICULTY_HEADER: &str = "x-pow-difficulty";
+
+/// Lifetime of the challenges issued by a [`PowGate`] unless another one is set with [`PowGate::with_ttl`].
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// `PowGate` issues the challenges of a gate and accepts each proof for them at most once.
+///
+/// Challenges are issued with [`ChallengeKey::issue`] for the difficulty of the HashFinder and an empty client
+/// binding. A proof is accepted if [`ChallengeKey::verify_signature`] accepts its challenge and
+/// [`HashFinder::check_and_consume`] accepts the proof, with the lifetime of the gate as the longest lifetime. The
+/// store is written with [`tokio::task::spawn_blocking`], after the signature and the proof passed, and a store that
+/// can't be reached rejects every proof.
+///
+/// Clones share the same store, so a gate can be handed to every worker of a service. Instances of a service behind
+/// a load balancer share the key and a store such as [`RedisSolutionStore`](crate::RedisSolutionStore).
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use pow_account::middleware::PowGate;
+/// use pow_account::{ChallengeKey, HashFinder, MemorySolutionStore};
+///
+/// let gate = PowGate::new(
+///     HashFinder::new(5),
+///     ChallengeKey::new([7u8; 32]),
+///     Arc::new(MemorySolutionStore::new()),
+/// );
+/// assert_eq!(gate.hash_finder(), &HashFinder::new(5));
+/// ```
+pub struct PowGate<D = Blake2s256> {
+    hash_finder: HashFinder<D>,
+    key: ChallengeKey,
+    store: Arc<dyn SolutionStore + Send + Sync>,
+    ttl: Duration,
+}
+
+impl<D> PowGate<D> {
+    /// Returns a gate that verifies proofs with the settings and difficulty of `hash_finder`
+    ///
+    /// # Parameters
+    ///
+    /// - `hash_finder`: The difficulty and settings of the proofs.
+    /// - `key`: The secret the challenges are signed with.
+    /// - `store`: The record of the proofs accepted so far.
+    pub fn new(
+        hash_finder: HashFinder<D>,
+        key: ChallengeKey,
+        store: Arc<dyn SolutionStore + Send + Sync>,
+    ) -> Self {
+        PowGate {
+            hash_finder,
+            key,
+            store,
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    /// Returns the gate with its challenges valid for `ttl` instead of [`DEFAULT_TTL`]
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        PowGate { ttl, ..self }
+    }
+
+    /// Returns the HashFinder the proofs are verified with
+    pub fn hash_finder(&self) -> &HashFinder<D> {
+        &self.hash_finder
+    }
+
+    /// Returns the lifetime of the challenges the gate issues
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+/// A proof that passed the checks of a [`PowGate`] but isn't recorded in its store yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Proof {
+    challenge: Challenge,
+    origin_hash: [u8; 32],
+    record_key: [u8; 32],
+}
+
+impl<D: Digest<OutputSize = U32>> PowGate<D> {
+    /// Returns the proof of the request if the gate issued the challenge and the proof meets the difficulty, without
+    /// looking at the store.
+    fn verified(&self, headers: &HeaderMap) -> Option<Proof> {
+        let challenge: Challenge = headers.get(CHALLENGE_HEADER)?.to_str().ok()?.parse().ok()?;
+        let origin_hash = decode_origin_hash(headers.get(SOLUTION_HEADER)?.as_bytes()).ok()?;
+
+        self.key
+            .verify_signature(&self.hash_finder, &challenge, &[])
+            .ok()?;
+        let record_key = self
+            .hash_finder
+            .record_key(&challenge, self.ttl, origin_hash)
+            .ok()??;
+        Some(Proof {
+            challenge,
+            origin_hash,
+            record_key,
+        })
+    }
+
+    /// Returns a `429 Too Many Requests` response with a fresh signed challenge and the required difficulty.
+    fn rejection<B: Default>(&self) -> Response<B> {
+        let mut response = Response::new(B::default());
+        *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+
+        let challenge = self.key.issue(&self.hash_finder, self.ttl, &[]);
+        let headers = response.headers_mut();
+        for (name, value) in [
+            (CHALLENGE_HEADER, challenge.to_string()),
+            (DIFFICULTY_HEADER, self.hash_finder.to_string()),
+        ] {
+            let value = HeaderValue::try_from(value)
+                .expect("hexadecimal and ASCII text are valid header values");
+            headers.insert(name, value);
+        }
+        response
+    }
+}
+
+impl<D> PowGate<D> {
+    /// Records a verified proof in the store on the blocking thread pool, so the caller can await it.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is not called from within a Tokio runtime.
+    fn consume(&self, proof: &Proof) -> JoinHandle<Result<bool, PowError>> {
+        let store = Arc::clone(&self.store);
+        let (record_key, expires_at) = (proof.record_key, proof.challenge.expires_at());
+
+        tokio::task::spawn_blocking(move || store.insert(record_key, expires_at))
+    }
+}
+
+/// Returns `true` if the proof was recorded by [`PowGate::consume`] now, rather than before or not at all. A panic of
+/// the store is resumed on the caller.
+fn consumed(joined: Result<Result<bool, PowError>, JoinError>) -> bool {
+    match joined {
+        Ok(recorded) => recorded == Ok(true),
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(_) => false,
+    }
+}
+
+impl<D> Clone for PowGate<D> {
+    fn clone(&self) -> Self {
+        PowGate {
+            hash_finder: self.hash_finder,
+            key: self.key.clone(),
+            store: Arc::clone(&self.store),
+            ttl: self.ttl,
+        }
+    }
+}
+
+/// Omits the key and the store.
+impl<D> fmt::Debug for PowGate<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PowGate")
+            .field("hash_finder", &self.hash_finder)
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// `PowLayer` wraps a service so that it only receives requests carrying a valid proof of work.
+///
+/// Rejected requests are answered by the layer with an empty body, as described in the [module](self)
+/// documentation, and never reach the inner service. The inner service is cloned for every request with a valid
+/// proof and called once the proof is recorded, while the original keeps serving new requests.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use pow_account::middleware::PowGate;
+/// use pow_account::{ChallengeKey, HashFinder, MemorySolutionStore, PowLayer};
+/// use tower::ServiceBuilder;
+///
+/// # fn handler(
+/// #     request: http::Request<String>,
+/// # ) -> std::future::Ready<Result<http::Response<String>, std::convert::Infallible>> {
+/// #     std::future::ready(Ok(http::Response::new(request.into_body())))
+/// # }
+/// let gate = PowGate::new(
+///     HashFinder::new(5),
+///     ChallengeKey::new([7u8; 32]),
+///     Arc::new(MemorySolutionStore::new()),
+/// );
+/// let service = ServiceBuilder::new()
+///     .layer(PowLayer::new(gate))
+///     .service_fn(handler);
+/// ```
+#[cfg(feature = "tower")]
+#[derive(Debug, Clone)]
+pub struct PowLayer<D = Blake2s256> {
+    gate: PowGate<D>,
+}
+
+#[cfg(feature = "tower")]
+impl<D> PowLayer<D> {
+    /// Returns a layer that verifies proofs with `gate`
+    pub fn new(gate: PowGate<D>) -> Self {
+        PowLayer { gate }
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<S, D> tower_layer::Layer<S> for PowLayer<D> {
+    type Service = PowService<S, D>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PowService {
+            inner,
+            gate: self.gate.clone(),
+        }
+    }
+}
+
+/// `PowService` is the service produced by [`PowLayer`].
+#[cfg(feature = "tower")]
+#[derive(Debug, Clone)]
+pub struct PowService<S, D = Blake2s256> {
+    inner: S,
+    gate: PowGate<D>,
+}
+
+#[cfg(feature = "tower")]
+impl<S, D, ReqBody, ResBody> tower_service::Service<http::Request<ReqBody>> for PowService<S, D>
+where
+    S: tower_service::Service<http::Request<ReqBody>, Response = Response<ResBody>> + Clone,
+    D: Digest<OutputSize = U32>,
+    ResBody: Default,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S, http::Request<ReqBody>, D>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        match self.gate.verified(request.headers()) {
+            Some(proof) => {
+                // The service that was driven to readiness handles the request, its clone the next ones
+                let clone = self.inner.clone();
+                let ready = core::mem::replace(&mut self.inner, clone);
+                ResponseFuture::Consuming {
+                    consume: self.gate.consume(&proof),
+                    gate: self.gate.clone(),
+                    call: Some((ready, request)),
+                }
+            }
+            None => ResponseFuture::Rejected {
+                response: Some(self.gate.rejection()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "tower")]
+pin_project_lite::pin_project! {
+    /// `ResponseFuture` is the future returned by [`PowService`], either the response of the inner service or the
+    /// rejection of the request.
+    #[project = ResponseFutureProj]
+    pub enum ResponseFuture<S, R, D = Blake2s256>
+    where
+        S: tower_service::Service<R>,
+    {
+        /// The proof was verified and is being recorded in the store.
+        Consuming {
+            consume: JoinHandle<Result<bool, PowError>>,
+            gate: PowGate<D>,
+            call: Option<(S, R)>,
+        },
+        /// The proof was recorded and the request passed to the inner service.
+        Inner {
+            #[pin]
+            future: S::Future,
+        },
+        /// The request was rejected.
+        Rejected {
+            response: Option<S::Response>,
+        },
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<S, R, D, B> Future for ResponseFuture<S, R, D>
+where
+    S: tower_service::Service<R, Response = Response<B>>,
+    D: Digest<OutputSize = U32>,
+    B: Default,
+{
+    type Output = Result<Response<B>, S::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            let next = match self.as_mut().project() {
+                ResponseFutureProj::Consuming {
+                    consume,
+                    gate,
+                    call,
+                } => match consumed(core::task::ready!(Pin::new(consume).poll(cx))) {
+                    true => {
+                        let (mut inner, request) =
+                            call.take().expect("the request is only forwarded once");
+                        ResponseFuture::Inner {
+                            future: inner.call(request),
+                        }
+                    }
+                    false => ResponseFuture::Rejected {
+                        response: Some(gate.rejection()),
+                    },
+                },
+                ResponseFutureProj::Inner { future } => return future.poll(cx),
+                ResponseFutureProj::Rejected { response } => {
+                    return Poll::Ready(Ok(response
+                        .take()
+                        .expect("the rejection is only returned once")))
+                }
+            };
+            self.set(next);
+        }
+    }
+}
+
+/// `PowSolution` extracts a verified proof of work from the headers of an axum request.
+///
+/// The proof is verified with the [`PowGate`] of the router state through [`FromRef`](axum_core::extract::FromRef),
+/// so the state is either a PowGate or a struct the PowGate can be obtained from. A request without a valid proof, or
+/// with a proof that was already accepted, is rejected with [`PowRejection`].
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use pow_account::middleware::PowGate;
+/// use pow_account::{ChallengeKey, HashFinder, MemorySolutionStore, PowSolution};
+///
+/// async fn register(solution: PowSolution) -> String {
+///     format!("registered with proof {}", hex::encode(solution.origin_hash))
+/// }
+///
+/// let gate = PowGate::new(
+///     HashFinder::new(5),
+///     ChallengeKey::new([7u8; 32]),
+///     Arc::new(MemorySolutionStore::new()),
+/// );
+/// let app: axum::Router = axum::Router::new()
+///     .route("/register", axum::routing::post(register))
+///     .with_state(gate);
+/// ```
+#[cfg(feature = "axum")]
+pub struct PowSolution<D = Blake2s256> {
+    /// The challenge the origin hash was found for.
+    pub challenge: Challenge,
+    /// The origin hash, which meets the difficulty for the challenge.
+    pub origin_hash: [u8; 32],
+    digest: PhantomData<fn() -> D>,
+}
+
+#[cfg(feature = "axum")]
+impl<D> fmt::Debug for PowSolution<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PowSolution")
+            .field("challenge", &self.challenge)
+            .field("origin_hash", &self.origin_hash)
+            .finish()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl<D> Clone for PowSolution<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "axum")]
+impl<D> Copy for PowSolution<D> {}
+
+#[cfg(feature = "axum")]
+impl<D> PartialEq for PowSolution<D> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.challenge, self.origin_hash) == (other.challenge, other.origin_hash)
+    }
+}
+
+#[cfg(feature = "axum")]
+impl<D> Eq for PowSolution<D> {}
+
+/// `PowRejection` is the rejection of a request without a valid proof of work, answered with
+/// `429 Too Many Requests` and a fresh challenge.
+#[cfg(feature = "axum")]
+#[derive(Debug, Clone)]
+pub struct PowRejection<D = Blake2s256> {
+    gate: PowGate<D>,
+}
+
+#[cfg(feature = "axum")]
+impl<D: Digest<OutputSize = U32>> axum_core::response::IntoResponse for PowRejection<D> {
+    fn into_response(self) -> axum_core::response::Response {
+        self.gate.rejection()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl<S, D> axum_core::extract::FromRequestParts<S> for PowSolution<D>
+where
+    S: Send + Sync,
+    D: Digest<OutputSize = U32>,
+    PowGate<D>: axum_core::extract::FromRef<S>,
+{
+    type Rejection = PowRejection<D>;
+
+    async fn from_request_parts(
+        parts: &mut http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let gate = <PowGate<D> as axum_core::extract::FromRef<S>>::from_ref(state);
+        let proof = match gate.verified(&parts.headers) {
+            Some(proof) => proof,
+            None => return Err(PowRejection { gate }),
+        };
+
+        match consumed(gate.consume(&proof).await) {
+            true => Ok(PowSolution {
+                challenge: proof.challenge,
+                origin_hash: proof.origin_hash,
+                digest: PhantomData,
+            }),
+            false => Err(PowRejection { gate }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::thread;
+    use std::time::Instant;
+
+    use crate::MemorySolutionStore;
+
+    use super::*;
+
+    const KEY: ChallengeKey = ChallengeKey::new([0x42; 32]);
+
+    /// A store that takes `delay` to answer, like a distant Redis server.
+    struct SlowStore {
+        delay: Duration,
+        records: MemorySolutionStore,
+    }
+
+    impl SolutionStore for SlowStore {
+        fn insert(&self, key: [u8; 32], expires_at: u64) -> Result<bool, PowError> {
+            thread::sleep(self.delay);
+            self.records.insert(key, expires_at)
+        }
+    }
+
+    fn gate(hash_finder: HashFinder) -> PowGate {
+        PowGate::new(hash_finder, KEY, Arc::new(MemorySolutionStore::new()))
+    }
+
+    fn slow_gate(hash_finder: HashFinder, delay: Duration) -> PowGate {
+        let store = SlowStore {
+            delay,
+            records: MemorySolutionStore::new(),
+        };
+        PowGate::new(hash_finder, KEY, Arc::new(store))
+    }
+
+    fn headers(challenge: &Challenge, origin_hash: &[u8; 32]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CHALLENGE_HEADER, challenge.to_string().parse().unwrap());
+        headers.insert(SOLUTION_HEADER, hex::encode(origin_hash).parse().unwrap());
+        headers
+    }
+
+    /// Returns the challenge issued in a rejection of the gate.
+    fn issued(gate: &PowGate) -> Challenge {
+        let response: Response<()> = gate.rejection();
+        response.headers()[CHALLENGE_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    /// Returns the challenge and origin hash of the request if the gate accepts it, recording the proof.
+    async fn accepted(gate: &PowGate, headers: &HeaderMap) -> Option<(Challenge, [u8; 32])> {
+        let proof = gate.verified(headers)?;
+        match consumed(gate.consume(&proof).await) {
+            true => Some((proof.challenge, proof.origin_hash)),
+            false => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn valid_proof_is_accepted_once() {
+        let gate = gate(HashFinder::new(3));
+        let challenge = issued(&gate);
+        let origin_hash = gate.hash_finder().find_with_challenge(&challenge);
+        let headers = headers(&challenge, &origin_hash);
+
+        assert_eq!(
+            accepted(&gate, &headers).await,
+            Some((challenge, origin_hash))
+        );
+        assert_eq!(accepted(&gate, &headers).await, None);
+        // Clones share the record of accepted proofs
+        assert_eq!(accepted(&gate.clone(), &headers).await, None)
+    }
+
+    #[test]
+    fn challenges_the_gate_did_not_issue_are_rejected() {
+        let gate = gate(HashFinder::new(2));
+        let hash_finder = *gate.hash_finder();
+
+        let other_key = ChallengeKey::new([0x43; 32]);
+        for challenge in [
+            Challenge::random(),
+            Challenge::issue(gate.ttl()),
+            other_key.issue(&hash_finder, gate.ttl(), &[]),
+            KEY.issue(&HashFinder::new(1), gate.ttl(), &[]),
+        ] {
+            let origin_hash = hash_finder.find_with_challenge(&challenge);
+            assert!(hash_finder
+                .check_with_challenge(&challenge, hex::encode(origin_hash))
+                .unwrap());
+            assert_eq!(gate.verified(&headers(&challenge, &origin_hash)), None)
+        }
+    }
+
+    #[test]
+    fn challenges_beyond_the_lifetime_of_the_gate_are_rejected() {
+        let gate = gate(HashFinder::new(2)).with_ttl(Duration::from_secs(60));
+        let challenge = KEY.issue(gate.hash_finder(), Duration::from_secs(3600), &[]);
+        let origin_hash = gate.hash_finder().find_with_challenge(&challenge);
+
+        assert_eq!(gate.verified(&headers(&challenge, &origin_hash)), None)
+    }
+
+    #[test]
+    fn missing_or_malformed_headers_are_not_verified() {
+        let gate = gate(HashFinder::new(0));
+        let challenge = issued(&gate);
+        let mut headers = headers(&challenge, &[0u8; 32]);
+
+        headers.insert(SOLUTION_HEADER, HeaderValue::from_static("abcd"));
+        assert_eq!(gate.verified(&headers), None);
+
+        headers.remove(SOLUTION_HEADER);
+        assert_eq!(gate.verified(&headers), None);
+
+        let mut headers = self::headers(&challenge, &[0u8; 32]);
+        headers.remove(CHALLENGE_HEADER);
+        assert_eq!(gate.verified(&headers), None);
+
+        let headers = self::headers(&challenge, &[0u8; 32]);
+        assert!(gate.verified(&headers).is_some())
+    }
+
+    #[test]
+    fn rejection_carries_a_signed_challenge_and_the_difficulty() {
+        let gate = gate(HashFinder::new(5));
+        let response: Response<()> = gate.rejection();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            response.headers()[DIFFICULTY_HEADER],
+            "pow-difficulty=20bit"
+        );
+
+        let challenge = issued(&gate);
+        assert_eq!(
+            KEY.verify_signature(gate.hash_finder(), &challenge, &[]),
+            Ok(())
+        );
+        assert_eq!(challenge.ttl(), DEFAULT_TTL.as_secs());
+        assert_eq!(
+            ChallengeKey::new([0x43; 32]).verify_signature(gate.hash_finder(), &challenge, &[]),
+            Err(PowError::InvalidSignature)
+        )
+    }
+
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn layer_only_forwards_verified_requests_once() {
+        use tower::{service_fn, Layer, ServiceExt};
+
+        let gate = gate(HashFinder::new(3));
+        let service = PowLayer::new(gate.clone()).layer(service_fn(|_| async {
+            Ok::<_, core::convert::Infallible>(Response::new(String::from("forwarded")))
+        }));
+
+        let response = service
+            .clone()
+            .oneshot(http::Request::new(()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.body().is_empty());
+
+        let challenge: Challenge = response.headers()[CHALLENGE_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let origin_hash = gate.hash_finder().find_with_challenge(&challenge);
+        let request = || {
+            let mut request = http::Request::new(());
+            *request.headers_mut() = headers(&challenge, &origin_hash);
+            request
+        };
+
+        let response = service.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), "forwarded");
+
+        let response = service.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn slow_store_does_not_block_the_runtime() {
+        use tower::{service_fn, Layer, ServiceExt};
+
+        let gate = slow_gate(HashFinder::new(3), Duration::from_millis(500));
+        let service = PowLayer::new(gate.clone()).layer(service_fn(|_| async {
+            Ok::<_, core::convert::Infallible>(Response::new(String::new()))
+        }));
+
+        let challenge = issued(&gate);
+        let origin_hash = gate.hash_finder().find_with_challenge(&challenge);
+        let mut request = http::Request::new(());
+        *request.headers_mut() = headers(&challenge, &origin_hash);
+
+        // The test runtime has a single thread, so the timer only fires on time if the request doesn't hold it
+        let started = Instant::now();
+        let response = tokio::spawn(service.oneshot(request));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(started.elapsed() < Duration::from_millis(400));
+        assert!(!response.is_finished());
+
+        assert_eq!(response.await.unwrap().unwrap().status(), StatusCode::OK)
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn extractor_reads_the_proof_from_the_headers_once() {
+        use axum_core::extract::FromRequestParts;
+        use axum_core::response::IntoResponse;
+
+        let gate = gate(HashFinder::new(3));
+        let challenge = issued(&gate);
+        let origin_hash = gate.hash_finder().find_with_challenge(&challenge);
+
+        let mut request = http::Request::new(());
+        *request.headers_mut() = headers(&challenge, &origin_hash);
+        let (mut parts, _) = request.into_parts();
+        let solution = PowSolution::<Blake2s256>::from_request_parts(&mut parts, &gate)
+            .await
+            .unwrap();
+        assert_eq!(
+            (solution.challenge, solution.origin_hash),
+            (challenge, origin_hash)
+        );
+
+        let replay = PowSolution::<Blake2s256>::from_request_parts(&mut parts, &gate).await;
+        assert!(replay.is_err());
+
+        let (mut parts, _) = http::Request::new(()).into_parts();
+        let rejection = PowSolution::<Blake2s256>::from_request_parts(&mut parts, &gate)
+            .await
+            .unwrap_err();
+        let response = rejection.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            response.headers()[DIFFICULTY_HEADER],
+            "pow-difficulty=12bit"
+        )
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn extractor_does_not_block_the_runtime_on_a_slow_store() {
+        use axum_core::extract::FromRequestParts;
+
+        let gate = slow_gate(HashFinder::new(3), Duration::from_millis(500));
+        let challenge = issued(&gate);
+        let origin_hash = gate.hash_finder().find_with_challenge(&challenge);
+
+        let mut request = http::Request::new(());
+        *request.headers_mut() = headers(&challenge, &origin_hash);
+        let (mut parts, _) = request.into_parts();
+
+        let started = Instant::now();
+        let extraction = tokio::spawn(async move {
+            PowSolution::<Blake2s256>::from_request_parts(&mut parts, &gate).await
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(started.elapsed() < Duration::from_millis(400));
+        assert!(!extraction.is_finished());
+
+        assert!(extraction.await.unwrap().is_ok())
+    }
+}
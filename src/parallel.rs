@@ -0,0 +1,190 @@
+//! Multi-threaded search and verification.
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+use rand_core::OsRng;
+
+use crate::{HashFinder, PowError, SaltCounter};
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Finds an origin hash using several threads
+    ///
+    /// Every worker runs the same search as [`HashFinder::find`] on its own entropy. The first worker to find an
+    /// origin hash sets a shared flag that makes the other workers stop at their next attempt, and all workers are
+    /// joined before the function returns, so no thread keeps running afterwards.
+    ///
+    /// # Parameters
+    ///
+    /// - `threads`: The number of worker threads. `0` uses the available parallelism of the machine.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let origin_hash = HashFinder::new(4).find_parallel(0);
+    /// assert!(HashFinder::new(4).check(hex::encode(origin_hash)).unwrap());
+    /// ```
+    pub fn find_parallel(&self, threads: usize) -> [u8; 32] {
+        let threads = match threads {
+            0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            threads => threads,
+        };
+        let found = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| scope.spawn(|| self.find_until(&found)))
+                .collect();
+
+            workers
+                .into_iter()
+                .filter_map(|worker| worker.join().expect("search worker panicked"))
+                .next()
+                .expect("the first worker to stop has found an origin hash")
+        })
+    }
+
+    /// Determines for each of several hexadecimal origin hashes whether it meets the requirement
+    ///
+    /// Every hash is checked as with [`HashFinder::check`], borrowing it rather than copying it. With the `parallel`
+    /// feature the batch is split into one chunk per available core and the chunks are checked on scoped threads;
+    /// without it the hashes are checked one after the other on the calling thread.
+    ///
+    /// # Parameters
+    ///
+    /// - `hashes`: The hexadecimal representations of the origin hashes to check.
+    ///
+    /// # Returns
+    ///
+    /// This function returns one result per hash, in the order of the input, with the same meaning as the result
+    /// of [`HashFinder::check`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hashes = vec![
+    ///     String::from("3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4"),
+    ///     String::from("not a hash"),
+    /// ];
+    /// let results = HashFinder::new(3).check_many(hashes);
+    ///
+    /// assert_eq!(results[0], Ok(true));
+    /// assert!(results[1].is_err());
+    /// ```
+    pub fn check_many<I>(&self, hashes: I) -> Vec<Result<bool, PowError>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]> + Sync,
+    {
+        let hashes: Vec<I::Item> = hashes.into_iter().collect();
+
+        #[cfg(feature = "parallel")]
+        {
+            let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+            let chunk_size = hashes.len().div_ceil(threads).max(1);
+
+            thread::scope(|scope| {
+                let workers: Vec<_> = hashes
+                    .chunks(chunk_size)
+                    .map(|chunk| scope.spawn(move || self.check_chunk(chunk)))
+                    .collect();
+
+                workers
+                    .into_iter()
+                    .flat_map(|worker| worker.join().expect("verification worker panicked"))
+                    .collect()
+            })
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        self.check_chunk(&hashes)
+    }
+
+    fn check_chunk<S: AsRef<[u8]>>(&self, hashes: &[S]) -> Vec<Result<bool, PowError>> {
+        hashes.iter().map(|hash| self.check(hash)).collect()
+    }
+
+    /// Searches until an origin hash is found or another worker has set `found`.
+    fn find_until(&self, found: &AtomicBool) -> Option<[u8; 32]> {
+        let mut counter = SaltCounter::reserve();
+        while !found.load(Ordering::Relaxed) {
+            if let Some(origin_hash) = self.attempt(&mut OsRng, &mut counter) {
+                found.store(true, Ordering::Relaxed);
+                return Some(origin_hash);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parallel_search_finds_a_valid_hash() {
+        let hash_finder = HashFinder::new(4);
+
+        for threads in [0, 1, 4] {
+            let origin_hash = hash_finder.find_parallel(threads);
+            assert!(hash_finder.check(hex::encode(origin_hash)).unwrap())
+        }
+    }
+
+    #[test]
+    fn batch_results_follow_the_input_order() {
+        let valid = "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e";
+        let insufficient = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+        let malformed = "3c+727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+
+        let hashes = [valid, insufficient, malformed, valid, &valid[..63]];
+        let results = HashFinder::new(4).check_many(hashes.map(String::from));
+
+        assert_eq!(
+            results,
+            vec![
+                Ok(true),
+                Ok(false),
+                Err(PowError::InvalidHex(
+                    hex::FromHexError::InvalidHexCharacter { c: '+', index: 2 }
+                )),
+                Ok(true),
+                Err(PowError::InvalidLength {
+                    len: 63,
+                    expected: 64
+                }),
+            ]
+        );
+        assert!(HashFinder::new(4)
+            .check_many(Vec::<String>::new())
+            .is_empty());
+        assert_eq!(HashFinder::new(4).check_many(hashes), results)
+    }
+
+    #[test]
+    fn large_batch_matches_individual_checks() {
+        let hash_finder = HashFinder::new(1);
+        let hashes: Vec<String> = (0..200u8).map(|index| hex::encode([index; 32])).collect();
+
+        let expected: Vec<_> = hashes.iter().map(|hash| hash_finder.check(hash)).collect();
+        assert_eq!(hash_finder.check_many(hashes), expected)
+    }
+
+    #[test]
+    fn workers_stop_once_the_flag_is_set() {
+        let found = AtomicBool::new(true);
+        assert_eq!(HashFinder::new(30).find_until(&found), None)
+    }
+}
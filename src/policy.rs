@@ -0,0 +1,310 @@
+//! Per-client difficulty that escalates with repeated requests and decays over time.
+//!
+//! A single difficulty is either too low to slow down an abusive client or too high for everybody else. A
+//! [`PolicyEngine`] keeps a decaying request count for every client key, such as an IP address, a session or an
+//! account, and issues harder challenges to the clients that keep coming back within the window.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::{HashFinder, MAX_DIFFICULTY_BITS};
+
+/// Window over which the requests of a client count towards its level when none is configured.
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Number of requests within the window that raise a client by one level when none is configured.
+pub const DEFAULT_REQUESTS_PER_LEVEL: u32 = 10;
+
+/// Number of bits added by every level when none is configured.
+pub const DEFAULT_BITS_PER_LEVEL: u32 = 2;
+
+/// Smallest number of clients before forgotten ones are evicted.
+const MIN_EVICTION_LEN: usize = 64;
+
+/// Decayed request count below which a client is forgotten.
+const FORGET_BELOW: f64 = 0.5;
+
+/// `PolicyEngine` maps client keys to difficulties.
+///
+/// Every client starts at the base difficulty of its tier, the engine's base difficulty unless
+/// [`PolicyEngine::set_tier`] assigned another one. Each request adds one to the request count of the client,
+/// which halves every window without requests. By default every 10 counted requests raise the client by a level and
+/// each level adds 2 bits, up to the maximum difficulty; [`PolicyEngine::with_escalation`] changes both.
+///
+/// The engine is shared between threads by reference. Clients whose count decayed are evicted whenever the number of
+/// clients has doubled since the last eviction.
+///
+/// # Example
+/// ```
+/// use pow_account::{HashFinder, PolicyEngine};
+///
+/// let policy = PolicyEngine::new(8, 24);
+///
+/// // The first requests of a client get the base difficulty
+/// assert_eq!(policy.record_request(b"203.0.113.7"), HashFinder::with_bits(8));
+///
+/// // A burst of requests escalates it
+/// for _ in 0..20 {
+///     policy.record_request(b"203.0.113.7");
+/// }
+/// assert_eq!(policy.bits_for(b"203.0.113.7"), 12);
+///
+/// // Other clients are not affected
+/// assert_eq!(policy.bits_for(b"198.51.100.1"), 8);
+/// ```
+#[derive(Debug)]
+pub struct PolicyEngine {
+    base_bits: u32,
+    max_bits: u32,
+    window: Duration,
+    requests_per_level: u32,
+    bits_per_level: u32,
+    clients: Mutex<Clients>,
+}
+
+#[derive(Debug, Default)]
+struct Clients {
+    counts: HashMap<Vec<u8>, Count>,
+    tiers: HashMap<Vec<u8>, u32>,
+    evict_at_len: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Count {
+    requests: f64,
+    updated_at: Instant,
+}
+
+impl PolicyEngine {
+    /// Returns a PolicyEngine issuing between `base_bits` and `max_bits` leading zero bits with the default escalation
+    ///
+    /// `max_bits` is raised to `base_bits` if it is lower, and both are capped at [`MAX_DIFFICULTY_BITS`].
+    pub fn new(base_bits: u32, max_bits: u32) -> Self {
+        let base_bits = base_bits.min(MAX_DIFFICULTY_BITS);
+        PolicyEngine {
+            base_bits,
+            max_bits: max_bits.clamp(base_bits, MAX_DIFFICULTY_BITS),
+            window: DEFAULT_WINDOW,
+            requests_per_level: DEFAULT_REQUESTS_PER_LEVEL,
+            bits_per_level: DEFAULT_BITS_PER_LEVEL,
+            clients: Mutex::default(),
+        }
+    }
+
+    /// Returns the PolicyEngine with another window, the time it takes the request count of a client to halve
+    ///
+    /// A zero window is raised to one millisecond.
+    pub fn with_window(self, window: Duration) -> Self {
+        PolicyEngine {
+            window: window.max(Duration::from_millis(1)),
+            ..self
+        }
+    }
+
+    /// Returns the PolicyEngine with another escalation: `bits_per_level` bits for every `requests_per_level`
+    /// counted requests
+    ///
+    /// `requests_per_level` is raised to 1 if it is 0.
+    pub fn with_escalation(self, requests_per_level: u32, bits_per_level: u32) -> Self {
+        PolicyEngine {
+            requests_per_level: requests_per_level.max(1),
+            bits_per_level,
+            ..self
+        }
+    }
+
+    /// Assigns a base difficulty to a client instead of the engine's
+    ///
+    /// Tiers let trusted accounts solve easier challenges and suspicious networks harder ones. The tier is capped at
+    /// the maximum difficulty and kept until [`PolicyEngine::clear_tier`] is called.
+    pub fn set_tier(&self, client: &[u8], base_bits: u32) {
+        self.clients()
+            .tiers
+            .insert(client.to_vec(), base_bits.min(self.max_bits));
+    }
+
+    /// Returns a client to the engine's base difficulty
+    pub fn clear_tier(&self, client: &[u8]) {
+        self.clients().tiers.remove(client);
+    }
+
+    /// Counts a request of `client` and returns the HashFinder to issue its challenge with
+    ///
+    /// The request is counted before the difficulty is chosen, so the request that completes a level already gets the
+    /// next difficulty.
+    pub fn record_request(&self, client: &[u8]) -> HashFinder {
+        HashFinder::with_bits(self.record_request_at(client, Instant::now()))
+    }
+
+    /// Returns the number of leading zero bits for the next challenge of `client`, without counting a request
+    pub fn bits_for(&self, client: &[u8]) -> u32 {
+        self.bits_at(client, Instant::now())
+    }
+
+    /// Returns the level of `client`, the number of times its difficulty was escalated
+    pub fn level(&self, client: &[u8]) -> u32 {
+        let clients = self.clients();
+        self.level_of(clients.counts.get(client), Instant::now())
+    }
+
+    /// Returns the number of clients with a request count, including forgotten ones that weren't evicted yet
+    pub fn len(&self) -> usize {
+        self.clients().counts.len()
+    }
+
+    /// Returns `true` if no client has a request count
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clients(&self) -> MutexGuard<'_, Clients> {
+        self.clients
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn record_request_at(&self, client: &[u8], now: Instant) -> u32 {
+        let mut clients = self.clients();
+        if clients.counts.len() >= clients.evict_at_len.max(MIN_EVICTION_LEN) {
+            clients.evict(self.window, now);
+        }
+
+        let requests = clients
+            .counts
+            .get(client)
+            .map_or(0.0, |count| count.decayed(self.window, now));
+        clients.counts.insert(
+            client.to_vec(),
+            Count {
+                requests: requests + 1.0,
+                updated_at: now,
+            },
+        );
+
+        self.bits_of(&clients, client, now)
+    }
+
+    fn bits_at(&self, client: &[u8], now: Instant) -> u32 {
+        self.bits_of(&self.clients(), client, now)
+    }
+
+    fn bits_of(&self, clients: &Clients, client: &[u8], now: Instant) -> u32 {
+        let base_bits = clients.tiers.get(client).copied().unwrap_or(self.base_bits);
+        let level = self.level_of(clients.counts.get(client), now);
+
+        level
+            .saturating_mul(self.bits_per_level)
+            .saturating_add(base_bits)
+            .min(self.max_bits)
+    }
+
+    fn level_of(&self, count: Option<&Count>, now: Instant) -> u32 {
+        let requests = count.map_or(0.0, |count| count.decayed(self.window, now));
+        (requests / self.requests_per_level as f64) as u32
+    }
+}
+
+impl Clients {
+    fn evict(&mut self, window: Duration, now: Instant) {
+        self.counts
+            .retain(|_, count| count.decayed(window, now) >= FORGET_BELOW);
+        self.evict_at_len = 2 * self.counts.len();
+    }
+}
+
+impl Count {
+    fn decayed(&self, window: Duration, now: Instant) -> f64 {
+        let windows =
+            now.saturating_duration_since(self.updated_at).as_secs_f64() / window.as_secs_f64();
+        self.requests * (-windows).exp2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn repeated_requests_escalate_the_difficulty() {
+        let policy = PolicyEngine::new(4, 20).with_escalation(5, 3);
+        let now = Instant::now();
+
+        for _ in 0..4 {
+            assert_eq!(policy.record_request_at(b"client", now), 4);
+        }
+        assert_eq!(policy.record_request_at(b"client", now), 7);
+        assert_eq!(policy.bits_at(b"client", now), 7);
+
+        for _ in 0..100 {
+            policy.record_request_at(b"client", now);
+        }
+        assert_eq!(policy.bits_at(b"client", now), 20);
+        assert_eq!(policy.bits_at(b"other", now), 4)
+    }
+
+    #[test]
+    fn request_count_halves_every_window() {
+        let policy = PolicyEngine::new(0, 64)
+            .with_window(Duration::from_secs(10))
+            .with_escalation(1, 1);
+        let now = Instant::now();
+
+        for _ in 0..8 {
+            policy.record_request_at(b"client", now);
+        }
+        assert_eq!(policy.bits_at(b"client", now), 8);
+        assert_eq!(policy.bits_at(b"client", now + Duration::from_secs(10)), 4);
+        assert_eq!(policy.bits_at(b"client", now + Duration::from_secs(30)), 1);
+
+        // New requests add to the decayed count
+        assert_eq!(
+            policy.record_request_at(b"client", now + Duration::from_secs(20)),
+            3
+        )
+    }
+
+    #[test]
+    fn tiers_override_the_base_difficulty() {
+        let policy = PolicyEngine::new(10, 16).with_escalation(1, 1);
+        let now = Instant::now();
+
+        policy.set_tier(b"trusted", 2);
+        policy.set_tier(b"suspicious", 30);
+
+        assert_eq!(policy.bits_at(b"trusted", now), 2);
+        assert_eq!(policy.bits_at(b"suspicious", now), 16);
+        assert_eq!(policy.record_request_at(b"trusted", now), 3);
+
+        policy.clear_tier(b"trusted");
+        assert_eq!(policy.bits_at(b"trusted", now), 11)
+    }
+
+    #[test]
+    fn forgotten_clients_are_evicted() {
+        let policy = PolicyEngine::new(0, 8).with_window(Duration::from_secs(1));
+        let now = Instant::now();
+
+        for client in 0..MIN_EVICTION_LEN as u32 {
+            policy.record_request_at(&client.to_be_bytes(), now);
+        }
+        assert_eq!(policy.len(), MIN_EVICTION_LEN);
+
+        policy.record_request_at(b"late", now + Duration::from_secs(2));
+        assert_eq!(policy.len(), 1);
+        assert_eq!(policy.level(b"late"), 0)
+    }
+
+    #[test]
+    fn bounds_are_ordered() {
+        let policy = PolicyEngine::new(12, 4).with_escalation(0, 1);
+        assert_eq!(policy.record_request(b"client"), HashFinder::with_bits(12));
+        assert!(!policy.is_empty());
+
+        assert_eq!(
+            PolicyEngine::new(300, 400).bits_for(b""),
+            MAX_DIFFICULTY_BITS
+        )
+    }
+}
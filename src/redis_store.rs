@@ -0,0 +1,286 @@
+//! A [`SolutionStore`] in Redis, enabled with the `redis` feature, so that several instances of a service share the
+//! record of accepted proofs.
+
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{PowError, SolutionStore};
+
+/// Prefix of the Redis keys of the records when none is given.
+pub const DEFAULT_KEY_PREFIX: &str = "pow-account:solution:";
+
+/// Longest time a connection, a request or a reply takes before the store is unavailable, when none is given.
+pub const DEFAULT_STORE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Number of idle connections kept for later records.
+const MAX_IDLE_CONNECTIONS: usize = 16;
+
+/// `RedisSolutionStore` records accepted proofs as Redis keys that expire with their challenge.
+///
+/// Every proof is recorded with a single `SET <key> 1 NX EXAT <expires_at>` command, which only succeeds for the first
+/// instance to record it and makes Redis evict the record once the challenge expired. The expiry comes from a challenge
+/// whose lifetime [`HashFinder::check_and_consume`](crate::HashFinder::check_and_consume) bounded first.
+///
+/// Each record takes an idle connection, or opens a new one, so concurrent records don't wait for each other, and
+/// the connection is kept for later records unless it failed. Connecting, sending the command and reading the reply
+/// each give up after the timeout of the store, so a server that hangs makes the store unavailable instead of
+/// blocking its callers.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use pow_account::{HashFinder, RedisSolutionStore};
+///
+/// let store = RedisSolutionStore::open("redis://127.0.0.1/").unwrap();
+/// # let (challenge, origin_hash) = (pow_account::Challenge::random(), "");
+/// let accepted =
+///     HashFinder::new(5).check_and_consume(&store, &challenge, Duration::from_secs(60), origin_hash);
+/// ```
+pub struct RedisSolutionStore {
+    client: redis::Client,
+    idle: Mutex<Vec<redis::Connection>>,
+    key_prefix: String,
+    timeout: Duration,
+}
+
+impl fmt::Debug for RedisSolutionStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisSolutionStore")
+            .field("client", &self.client)
+            .field("key_prefix", &self.key_prefix)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedisSolutionStore {
+    /// Returns a store for the Redis server at `url`, such as `redis://127.0.0.1/`
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::InvalidFormat`] if `url` is not a valid Redis URL. The server is only
+    /// contacted when the first proof is recorded.
+    pub fn open(url: &str) -> Result<Self, PowError> {
+        let client = redis::Client::open(url).map_err(|_| PowError::InvalidFormat)?;
+        Ok(RedisSolutionStore {
+            client,
+            idle: Mutex::new(Vec::new()),
+            key_prefix: String::from(DEFAULT_KEY_PREFIX),
+            timeout: DEFAULT_STORE_TIMEOUT,
+        })
+    }
+
+    /// Returns the store with the keys of its records starting with `key_prefix` instead of [`DEFAULT_KEY_PREFIX`]
+    pub fn with_key_prefix(mut self, key_prefix: &str) -> Self {
+        self.key_prefix = String::from(key_prefix);
+        self
+    }
+
+    /// Returns the store with connections, requests and replies given up after `timeout` instead of
+    /// [`DEFAULT_STORE_TIMEOUT`]
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `timeout` is zero.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        assert!(
+            !timeout.is_zero(),
+            "the timeout of a RedisSolutionStore must not be zero"
+        );
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns an idle connection, or a new one if none is idle.
+    fn connection(&self) -> redis::RedisResult<redis::Connection> {
+        if let Some(connection) = self.idle_connections().pop() {
+            return Ok(connection);
+        }
+
+        let connection = self.client.get_connection_with_timeout(self.timeout)?;
+        connection.set_read_timeout(Some(self.timeout))?;
+        connection.set_write_timeout(Some(self.timeout))?;
+        Ok(connection)
+    }
+
+    fn idle_connections(&self) -> std::sync::MutexGuard<'_, Vec<redis::Connection>> {
+        self.idle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn redis_key(&self, key: &[u8; 32]) -> String {
+        format!("{}{}", self.key_prefix, hex::encode(key))
+    }
+}
+
+impl SolutionStore for RedisSolutionStore {
+    fn insert(&self, key: [u8; 32], expires_at: u64) -> Result<bool, PowError> {
+        let mut connection = self.connection().map_err(|_| PowError::StoreUnavailable)?;
+
+        // No lock is held during the round trip, a failed connection is dropped
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(self.redis_key(&key))
+            .arg(1)
+            .arg("NX")
+            .arg("EXAT")
+            .arg(expires_at)
+            .query(&mut connection)
+            .map_err(|_| PowError::StoreUnavailable)?;
+
+        let mut idle = self.idle_connections();
+        if idle.len() < MAX_IDLE_CONNECTIONS {
+            idle.push(connection);
+        }
+        Ok(reply.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use std::time::Instant;
+
+    use super::*;
+
+    /// Serves the commands of one connection, answering `SET` with the given replies and everything else with `OK`,
+    /// and returns the `SET` commands it received.
+    fn serve(listener: TcpListener, set_replies: &'static [&'static str]) -> Vec<Vec<String>> {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let mut set_replies = set_replies.iter();
+        let mut set_commands = Vec::new();
+
+        while let Some(args) = read_command(&mut reader) {
+            let reply = match args[0].as_str() {
+                "SET" => {
+                    set_commands.push(args);
+                    match set_replies.next() {
+                        Some(reply) => *reply,
+                        None => break,
+                    }
+                }
+                _ => "+OK\r\n",
+            };
+            writer.write_all(reply.as_bytes()).unwrap();
+        }
+        set_commands
+    }
+
+    /// Reads the arguments of the next command, or `None` once the connection is closed.
+    fn read_command(reader: &mut impl BufRead) -> Option<Vec<String>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+
+        let argc: usize = line.trim().trim_start_matches('*').parse().unwrap();
+        let mut args = Vec::new();
+        for _ in 0..argc {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            args.push(line.trim_end().to_owned());
+        }
+        Some(args)
+    }
+
+    #[test]
+    fn records_are_set_only_if_absent_until_they_expire() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("redis://{}/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || serve(listener, &["+OK\r\n", "$-1\r\n"]));
+
+        let store = RedisSolutionStore::open(&url)
+            .unwrap()
+            .with_key_prefix("test:");
+        assert_eq!(store.insert([0xab; 32], 1_700_000_000), Ok(true));
+        assert_eq!(store.insert([0xab; 32], 1_700_000_000), Ok(false));
+        drop(store);
+
+        let key = format!("test:{}", "ab".repeat(32));
+        let set = ["SET", &key, "1", "NX", "EXAT", "1700000000"].map(String::from);
+        assert_eq!(server.join().unwrap(), vec![set.to_vec(), set.to_vec()])
+    }
+
+    #[test]
+    fn unreachable_server_makes_the_store_unavailable() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("redis://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let store = RedisSolutionStore::open(&url).unwrap();
+        assert_eq!(store.insert([0; 32], 0), Err(PowError::StoreUnavailable));
+        assert_eq!(
+            RedisSolutionStore::open("not a url").unwrap_err(),
+            PowError::InvalidFormat
+        )
+    }
+
+    #[test]
+    fn concurrent_records_use_separate_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("redis://{}/", listener.local_addr().unwrap());
+
+        // Each connection only gets its reply once both received their SET, which a single connection never does
+        let both_received = Arc::new(Barrier::new(2));
+        let server = thread::spawn(move || {
+            let connections: Vec<_> = (0..2)
+                .map(|_| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let both_received = Arc::clone(&both_received);
+                    thread::spawn(move || {
+                        let mut reader = BufReader::new(stream.try_clone().unwrap());
+                        let mut writer = stream;
+                        while let Some(args) = read_command(&mut reader) {
+                            if args[0] == "SET" {
+                                both_received.wait();
+                            }
+                            writer.write_all(b"+OK\r\n").unwrap();
+                        }
+                    })
+                })
+                .collect();
+            connections
+                .into_iter()
+                .for_each(|connection| connection.join().unwrap())
+        });
+
+        let store = &RedisSolutionStore::open(&url).unwrap();
+        thread::scope(|scope| {
+            let records: Vec<_> = [[1; 32], [2; 32]]
+                .map(|key| scope.spawn(move || store.insert(key, 1_700_000_000)))
+                .into_iter()
+                .map(|record| record.join().unwrap())
+                .collect();
+            assert_eq!(records, [Ok(true), Ok(true)])
+        });
+        assert_eq!(store.idle_connections().len(), 2);
+
+        store.idle_connections().clear();
+        server.join().unwrap()
+    }
+
+    #[test]
+    fn hung_server_makes_the_store_unavailable_after_the_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("redis://{}/", listener.local_addr().unwrap());
+        // The connection is accepted by the operating system, but nothing is ever read or answered
+        let store = RedisSolutionStore::open(&url)
+            .unwrap()
+            .with_timeout(Duration::from_millis(100));
+
+        let started = Instant::now();
+        assert_eq!(store.insert([0; 32], 0), Err(PowError::StoreUnavailable));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(store.idle_connections().is_empty());
+        drop(listener)
+    }
+}
@@ -0,0 +1,47 @@
+//! Runtime smoke test of the hashing construction.
+
+use crate::HashFinder;
+
+/// An origin hash whose target hash starts with 12 zero bits.
+const KNOWN_GOOD: &str = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+
+/// An origin hash whose target hash starts with a single zero bit.
+const KNOWN_BAD: &str = "c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa";
+
+/// Checks that finding and checking agree in this build of the crate
+///
+/// For 1 and 2 leading zeros an origin hash is found and checked, a known origin hash is checked against
+/// the difficulty it is known to pass, and another one against a difficulty it is known to fail. The known
+/// vectors catch a digest backend that produces wrong hashes even if it is self-consistent. All difficulties
+/// are low, so the test completes quickly and can be called at startup.
+///
+/// # Returns
+///
+/// This function returns `true` if every step behaves as expected, otherwise it returns `false`.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(pow_account::self_test());
+/// ```
+pub fn self_test() -> bool {
+    let found_hashes_pass = [1, 2].into_iter().all(|leading_zeros| {
+        let hash_finder = HashFinder::new(leading_zeros);
+        matches!(hash_finder.check(hex::encode(hash_finder.find())), Ok(true))
+    });
+
+    found_hashes_pass
+        && matches!(HashFinder::new(3).check(String::from(KNOWN_GOOD)), Ok(true))
+        && matches!(HashFinder::new(1).check(String::from(KNOWN_BAD)), Ok(false))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn self_test_passes() {
+        assert!(self_test())
+    }
+}
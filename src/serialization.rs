@@ -0,0 +1,375 @@
+//! Serde support for [`HashFinder`], [`Challenge`] and [`Solution`], enabled with the `serde` feature.
+//!
+//! A HashFinder is serialized as a struct with its target, floor, version, counter salt, number of rounds and domain
+//! tag. The 256-bit values are written as hexadecimal strings in human-readable formats such as JSON or TOML
+//! and as raw bytes in binary formats such as bincode. Only the target is required when deserializing, the other
+//! fields default to the values of [`HashFinder::from_target`].
+//!
+//! The digest is a type parameter of the HashFinder and is not serialized: settings are deserialized with the digest
+//! of the type they are deserialized into, so a service that uses another digest than Blake2s names it in that type,
+//! as in `HashFinder<Sha256>`, wherever it loads settings.
+//!
+//! A Challenge is serialized as its 32 bytes alone, so it is a plain hexadecimal string in JSON. A Solution is
+//! serialized as a struct with its origin hash and target hash encoded the same way, its number of attempts and its
+//! elapsed time in the usual serde representation of a [`Duration`].
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::{Challenge, HashFinder, Solution, DEFAULT_ROUNDS, DEFAULT_VERSION};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "HashFinder")]
+struct Settings {
+    #[serde(with = "hex_or_bytes")]
+    target: [u8; 32],
+    #[serde(with = "hex_or_bytes", default)]
+    floor: [u8; 32],
+    #[serde(default = "default_version")]
+    version: u8,
+    #[serde(default = "default_counter_salt")]
+    counter_salt: bool,
+    #[serde(default = "default_rounds")]
+    rounds: u32,
+    #[serde(default)]
+    domain: Option<DomainTag>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct DomainTag(#[serde(with = "hex_or_bytes")] [u8; 32]);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Solution")]
+struct SolutionFields {
+    #[serde(with = "hex_or_bytes")]
+    origin_hash: [u8; 32],
+    #[serde(with = "hex_or_bytes")]
+    target_hash: [u8; 32],
+    attempts: u64,
+    elapsed: Duration,
+}
+
+fn default_version() -> u8 {
+    DEFAULT_VERSION
+}
+
+fn default_counter_salt() -> bool {
+    true
+}
+
+fn default_rounds() -> u32 {
+    DEFAULT_ROUNDS
+}
+
+impl<D> Serialize for HashFinder<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Settings {
+            target: self.target,
+            floor: self.floor,
+            version: self.version,
+            counter_salt: self.counter_salt,
+            rounds: self.rounds,
+            domain: self.domain.map(DomainTag),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, D> Deserialize<'de> for HashFinder<D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let settings = Settings::deserialize(deserializer)?;
+        if settings.floor != [0u8; 32] && settings.floor >= settings.target {
+            return Err(de::Error::custom("the floor must be lower than the target"));
+        }
+        if settings.rounds < DEFAULT_ROUNDS {
+            return Err(de::Error::custom(
+                "at least 2 rounds of hashing are required",
+            ));
+        }
+
+        Ok(HashFinder {
+            target: settings.target,
+            floor: settings.floor,
+            version: settings.version,
+            counter_salt: settings.counter_salt,
+            rounds: settings.rounds,
+            domain: settings.domain.map(|DomainTag(domain_tag)| domain_tag),
+            digest: PhantomData,
+        })
+    }
+}
+
+impl Serialize for Challenge {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        hex_or_bytes::serialize(&self.bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Challenge {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        hex_or_bytes::deserialize(deserializer).map(Challenge::from_bytes)
+    }
+}
+
+impl Serialize for Solution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SolutionFields {
+            origin_hash: self.origin_hash,
+            target_hash: self.target_hash,
+            attempts: self.attempts,
+            elapsed: self.elapsed,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Solution {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let fields = SolutionFields::deserialize(deserializer)?;
+        Ok(Solution {
+            origin_hash: fields.origin_hash,
+            target_hash: fields.target_hash,
+            attempts: fields.attempts,
+            elapsed: fields.elapsed,
+        })
+    }
+}
+
+/// Serializes a 256-bit value as a hexadecimal string or as raw bytes depending on the format.
+mod hex_or_bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.serialize_str(&hex::encode(value)),
+            false => serializer.serialize_bytes(value),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        match deserializer.is_human_readable() {
+            true => deserializer.deserialize_str(ValueVisitor),
+            false => deserializer.deserialize_bytes(ValueVisitor),
+        }
+    }
+
+    struct ValueVisitor;
+
+    impl<'de> Visitor<'de> for ValueVisitor {
+        type Value = [u8; 32];
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("32 bytes or 64 hexadecimal characters")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            let mut value = [0u8; 32];
+            hex::decode_to_slice(v, &mut value).map_err(|err| match err {
+                hex::FromHexError::OddLength | hex::FromHexError::InvalidStringLength => {
+                    E::invalid_length(v.len(), &"64 hexadecimal characters")
+                }
+                err => E::custom(err),
+            })?;
+            Ok(value)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            v.try_into()
+                .map_err(|_| E::invalid_length(v.len(), &"32 bytes"))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut value = [0u8; 32];
+            for (index, byte) in value.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(index, &"32 bytes"))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(de::Error::invalid_length(33, &"32 bytes"));
+            }
+            Ok(value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use sha2::Sha256;
+
+    use super::*;
+
+    #[test]
+    fn json_round_trip_uses_hexadecimal_strings() {
+        let hash_finder = HashFinder::with_version(4, 2);
+
+        let json = serde_json::to_string(&hash_finder).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"target":"0000{}","floor":"{}","version":2,"counter_salt":true,"rounds":2,"domain":null}}"#,
+                "f".repeat(60),
+                "0".repeat(64)
+            )
+        );
+
+        let restored: HashFinder = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, hash_finder)
+    }
+
+    #[test]
+    fn json_only_requires_the_target() {
+        let json = format!(r#"{{"target":"0000{}"}}"#, "f".repeat(60));
+
+        let hash_finder: HashFinder = serde_json::from_str(&json).unwrap();
+        assert_eq!(hash_finder, HashFinder::new(4))
+    }
+
+    #[test]
+    fn json_rejects_targets_of_the_wrong_length() {
+        let json = format!(r#"{{"target":"0000{}"}}"#, "f".repeat(58));
+        let err = serde_json::from_str::<HashFinder>(&json).unwrap_err();
+        assert!(err.to_string().contains("64 hexadecimal characters"));
+
+        let json = format!(r#"{{"target":"0000{}"}}"#, "f".repeat(62));
+        assert!(serde_json::from_str::<HashFinder>(&json).is_err());
+
+        let json = format!(r#"{{"target":"zz00{}"}}"#, "f".repeat(60));
+        assert!(serde_json::from_str::<HashFinder>(&json).is_err())
+    }
+
+    #[test]
+    fn bincode_round_trip_uses_raw_bytes() {
+        let mut lo = [0u8; 32];
+        lo[0] = 0x10;
+        let mut hi = [0u8; 32];
+        hi[0] = 0x20;
+        let hash_finder = HashFinder::with_band(lo, hi).unwrap();
+
+        let bytes = bincode::serialize(&hash_finder).unwrap();
+        assert_eq!(bytes.len(), 2 * (8 + 32) + 2 + 4 + 1);
+
+        let restored: HashFinder = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored, hash_finder)
+    }
+
+    #[test]
+    fn bincode_rejects_targets_of_the_wrong_length() {
+        let mut bytes = bincode::serialize(&HashFinder::new(4)).unwrap();
+        bytes[0] = 31;
+
+        let err = bincode::deserialize::<HashFinder>(&bytes).unwrap_err();
+        assert!(err.to_string().contains("32 bytes"))
+    }
+
+    #[test]
+    fn digest_is_chosen_by_the_deserialized_type() {
+        let hash_finder = HashFinder::new(4).with_digest::<Sha256>();
+
+        // The settings are the same bytes whatever the digest, so they don't depend on how the compiler names it
+        let json = serde_json::to_string(&hash_finder).unwrap();
+        assert_eq!(json, serde_json::to_string(&HashFinder::new(4)).unwrap());
+
+        let restored: HashFinder<Sha256> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, hash_finder);
+
+        let bytes = bincode::serialize(&hash_finder).unwrap();
+        let restored: HashFinder<Sha256> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored, hash_finder)
+    }
+
+    #[test]
+    fn floor_must_be_lower_than_the_target() {
+        let json = format!(
+            r#"{{"target":"10{}","floor":"20{}"}}"#,
+            "0".repeat(62),
+            "0".repeat(62)
+        );
+        assert!(serde_json::from_str::<HashFinder>(&json).is_err())
+    }
+
+    #[test]
+    fn fewer_than_two_rounds_are_rejected() {
+        let json = format!(r#"{{"target":"0000{}","rounds":3}}"#, "f".repeat(60));
+        let hash_finder: HashFinder = serde_json::from_str(&json).unwrap();
+        assert_eq!(hash_finder, HashFinder::new(4).with_rounds(3));
+
+        let json = format!(r#"{{"target":"0000{}","rounds":1}}"#, "f".repeat(60));
+        assert!(serde_json::from_str::<HashFinder>(&json).is_err())
+    }
+
+    #[test]
+    fn domain_tag_round_trips() {
+        let hash_finder = HashFinder::new(4).with_domain(b"service-a");
+
+        let json = serde_json::to_string(&hash_finder).unwrap();
+        let restored: HashFinder = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, hash_finder);
+
+        let bytes = bincode::serialize(&hash_finder).unwrap();
+        let restored: HashFinder = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored, hash_finder)
+    }
+
+    #[test]
+    fn challenge_is_a_hexadecimal_string_in_json() {
+        let challenge = Challenge::from_bytes([0xab; 32]);
+
+        let json = serde_json::to_string(&challenge).unwrap();
+        assert_eq!(json, format!(r#""{}""#, "ab".repeat(32)));
+        assert_eq!(serde_json::from_str::<Challenge>(&json).unwrap(), challenge);
+
+        let bytes = bincode::serialize(&challenge).unwrap();
+        assert_eq!(bytes.len(), 8 + 32);
+        assert_eq!(
+            bincode::deserialize::<Challenge>(&bytes).unwrap(),
+            challenge
+        );
+
+        assert!(serde_json::from_str::<Challenge>(r#""abab""#).is_err())
+    }
+
+    #[test]
+    fn solution_round_trips_with_hexadecimal_hashes() {
+        let solution = Solution {
+            origin_hash: [0x51; 32],
+            target_hash: [0x00; 32],
+            attempts: 1234,
+            elapsed: Duration::from_millis(1500),
+        };
+
+        let json = serde_json::to_string(&solution).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"origin_hash":"{}","target_hash":"{}","attempts":1234,"elapsed":{{"secs":1,"nanos":500000000}}}}"#,
+                "51".repeat(32),
+                "0".repeat(64)
+            )
+        );
+        assert_eq!(serde_json::from_str::<Solution>(&json).unwrap(), solution);
+
+        let bytes = bincode::serialize(&solution).unwrap();
+        assert_eq!(bincode::deserialize::<Solution>(&bytes).unwrap(), solution)
+    }
+
+    #[test]
+    fn found_solution_round_trips() {
+        let hash_finder = HashFinder::new(2);
+        let solution = hash_finder.find_with_stats();
+
+        let json = serde_json::to_string(&solution).unwrap();
+        let restored: Solution = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, solution);
+        assert!(hash_finder
+            .check(hex::encode(restored.origin_hash))
+            .unwrap())
+    }
+}
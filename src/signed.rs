@@ -0,0 +1,238 @@
+//! Challenges signed by the server, which any instance holding the key can verify without shared state.
+//!
+//! A [`ChallengeKey`] issues challenges laid out like those of [`Challenge::issue`], except that the last 20 bytes
+//! are a 4-byte random salt followed by a 16-byte keyed Blake2s MAC. The MAC covers the issue time, the lifetime and
+//! the salt of the challenge, the difficulty of the [`HashFinder`] it was issued for and an optional client binding,
+//! such as the IP address or account of the client, so none of them can be changed without the key.
+
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+#[cfg(feature = "std")]
+use blake2::digest::consts::U32;
+use blake2::digest::Mac;
+use blake2::Blake2sMac256;
+#[cfg(feature = "std")]
+use blake2::Digest;
+use subtle::ConstantTimeEq;
+
+use crate::{Challenge, HashFinder, PowError};
+
+/// Personalization of the keyed Blake2s MAC, which separates it from other uses of the same key.
+const MAC_PERSONAL: &[u8; 8] = b"powchlng";
+
+/// Number of bytes of the MAC kept at the end of a challenge.
+const MAC_LEN: usize = 16;
+
+/// `ChallengeKey` is a server secret that signs and verifies challenges.
+///
+/// The key should be 32 random bytes shared by every instance of the service, and kept secret: anybody who knows it
+/// can issue challenges.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use pow_account::{ChallengeKey, HashFinder};
+///
+/// let key = ChallengeKey::new([7u8; 32]);
+/// let hash_finder = HashFinder::new(3);
+///
+/// // Server
+/// let challenge = key.issue(&hash_finder, Duration::from_secs(60), b"203.0.113.7");
+///
+/// // Client
+/// let origin_hash = hash_finder.find_with_challenge(&challenge);
+///
+/// // Any server instance
+/// assert!(key.verify(&hash_finder, &challenge, b"203.0.113.7", hex::encode(origin_hash)).unwrap());
+/// ```
+#[derive(Clone)]
+pub struct ChallengeKey {
+    key: [u8; 32],
+}
+
+/// Omits the key, so that it doesn't end up in logs.
+impl core::fmt::Debug for ChallengeKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ChallengeKey").finish_non_exhaustive()
+    }
+}
+
+impl ChallengeKey {
+    /// Returns a key from 32 secret bytes
+    pub const fn new(key: [u8; 32]) -> Self {
+        ChallengeKey { key }
+    }
+
+    /// Issues a signed challenge that expires `ttl` after now
+    ///
+    /// # Parameters
+    ///
+    /// - `hash_finder`: The HashFinder the proof will be verified with. Its difficulty is signed with the challenge.
+    /// - `ttl`: The lifetime of the challenge, rounded down to whole seconds and saturating at `u32::MAX` seconds.
+    /// - `client`: Bytes identifying the client, which must be given again to [`ChallengeKey::verify`]. It may be
+    ///   empty.
+    ///
+    /// # Panics
+    ///
+    /// This function panics on targets without a system clock, such as `wasm32-unknown-unknown`.
+    #[cfg(feature = "std")]
+    pub fn issue<D>(&self, hash_finder: &HashFinder<D>, ttl: Duration, client: &[u8]) -> Challenge {
+        // The bytes after the times are random, the first four of them are kept as the salt
+        self.sign(hash_finder, Challenge::issue(ttl).bytes(), client)
+    }
+
+    /// Determines whether an origin hash was found for a challenge issued with this key, still valid and bound to
+    /// `client`
+    ///
+    /// The signature is checked first, then the lifetime of the challenge and finally the proof, as with
+    /// [`HashFinder::check_with_challenge`].
+    ///
+    /// # Parameters
+    ///
+    /// - `hash_finder`: The HashFinder the challenge was issued for.
+    /// - `challenge`: The challenge as sent back by the client.
+    /// - `client`: The bytes identifying the client given to [`ChallengeKey::issue`].
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::InvalidSignature`] if the challenge was not issued with this key for the
+    /// difficulty of `hash_finder` and for `client`, the errors of [`Challenge::validate`] if it is not valid now,
+    /// and an error if the provided hash string is not a valid hexadecimal representation.
+    #[cfg(feature = "std")]
+    pub fn verify<D, S>(
+        &self,
+        hash_finder: &HashFinder<D>,
+        challenge: &Challenge,
+        client: &[u8],
+        origin_hash: S,
+    ) -> Result<bool, PowError>
+    where
+        D: Digest<OutputSize = U32>,
+        S: AsRef<[u8]>,
+    {
+        // The lifetime is covered by the signature, so it is the one the server chose
+        self.verify_signature(hash_finder, challenge, client)?;
+        challenge.validate()?;
+        hash_finder.check_with_challenge(challenge, origin_hash)
+    }
+
+    /// Determines whether a challenge was issued with this key for the difficulty of `hash_finder` and for `client`
+    ///
+    /// Only the signature is checked, in constant time, so this also works without `std`. Its lifetime can then be
+    /// checked with [`Challenge::validate_at`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::InvalidSignature`] if the signature doesn't match.
+    pub fn verify_signature<D>(
+        &self,
+        hash_finder: &HashFinder<D>,
+        challenge: &Challenge,
+        client: &[u8],
+    ) -> Result<(), PowError> {
+        let nonce = challenge.bytes();
+        let expected = self.sign(hash_finder, nonce, client).bytes();
+
+        match bool::from(nonce[32 - MAC_LEN..].ct_eq(&expected[32 - MAC_LEN..])) {
+            true => Ok(()),
+            false => Err(PowError::InvalidSignature),
+        }
+    }
+
+    /// Returns the challenge with its last bytes replaced by the MAC of the others, the difficulty and `client`.
+    fn sign<D>(
+        &self,
+        hash_finder: &HashFinder<D>,
+        mut nonce: [u8; 32],
+        client: &[u8],
+    ) -> Challenge {
+        let mut mac = Blake2sMac256::new_with_salt_and_personal(&self.key, &[], MAC_PERSONAL)
+            .expect("a 32-byte key is accepted by Blake2s");
+        mac.update(&nonce[..32 - MAC_LEN]);
+        mac.update(&hash_finder.target);
+        mac.update(&hash_finder.floor);
+        mac.update(client);
+
+        nonce[32 - MAC_LEN..].copy_from_slice(&mac.finalize().into_bytes()[..MAC_LEN]);
+        Challenge::from_bytes(nonce)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+
+    const KEY: ChallengeKey = ChallengeKey::new([0x42; 32]);
+
+    #[test]
+    fn signed_challenge_is_verified_by_the_same_key() {
+        let hash_finder = HashFinder::new(2);
+        let challenge = KEY.issue(&hash_finder, Duration::from_secs(60), b"client");
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+
+        assert_eq!(
+            KEY.verify(&hash_finder, &challenge, b"client", &origin_hash),
+            Ok(true)
+        );
+        assert_eq!(challenge.expires_at(), challenge.issued_at() + 60);
+        assert_ne!(
+            KEY.issue(&hash_finder, Duration::from_secs(60), b"client"),
+            challenge
+        )
+    }
+
+    #[test]
+    fn tampered_bindings_are_rejected() {
+        let hash_finder = HashFinder::new(2);
+        let challenge = KEY.issue(&hash_finder, Duration::from_secs(60), b"client");
+
+        let other_key = ChallengeKey::new([0x43; 32]);
+        assert_eq!(
+            other_key.verify_signature(&hash_finder, &challenge, b"client"),
+            Err(PowError::InvalidSignature)
+        );
+        assert_eq!(
+            KEY.verify_signature(&hash_finder, &challenge, b"other client"),
+            Err(PowError::InvalidSignature)
+        );
+        assert_eq!(
+            KEY.verify_signature(&HashFinder::new(1), &challenge, b"client"),
+            Err(PowError::InvalidSignature)
+        );
+
+        // Extending the lifetime changes the signed bytes
+        let mut nonce = challenge.bytes();
+        nonce[11] = nonce[11].wrapping_add(1);
+        assert_eq!(
+            KEY.verify_signature(&hash_finder, &Challenge::from_bytes(nonce), b"client"),
+            Err(PowError::InvalidSignature)
+        )
+    }
+
+    #[test]
+    fn signature_is_checked_before_the_proof() {
+        let hash_finder = HashFinder::new(2);
+        let challenge = KEY.issue(&hash_finder, Duration::ZERO, b"");
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+
+        assert_eq!(KEY.verify_signature(&hash_finder, &challenge, b""), Ok(()));
+        assert_eq!(
+            KEY.verify(&hash_finder, &challenge, b"", &origin_hash),
+            Err(PowError::Expired {
+                expires_at: challenge.issued_at()
+            })
+        );
+        assert_eq!(
+            KEY.verify(&hash_finder, &Challenge::random(), b"", &origin_hash),
+            Err(PowError::InvalidSignature)
+        )
+    }
+
+    #[test]
+    fn debug_output_omits_the_key() {
+        assert_eq!(format!("{KEY:?}"), "ChallengeKey { .. }")
+    }
+}
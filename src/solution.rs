@@ -0,0 +1,212 @@
+//! Origin hashes together with metadata about the search that found them, and progress reports of running searches.
+
+use std::time::{Duration, Instant};
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+use rand_core::OsRng;
+
+use crate::{leading_zero_bits, HashFinder, SaltCounter};
+
+/// `Solution` is an origin hash returned by [`HashFinder::find_with_stats`] with the metrics of its search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Solution {
+    /// The origin hash to send to the verifier.
+    pub origin_hash: [u8; 32],
+    /// The target hash the verifier will compute from the origin hash.
+    pub target_hash: [u8; 32],
+    /// The number of candidates hashed, including the successful one.
+    pub attempts: u64,
+    /// The wall-clock time the search took.
+    pub elapsed: Duration,
+}
+
+impl Solution {
+    /// Returns the number of leading zero bits of the target hash, which is at least the required difficulty
+    pub fn achieved_bits(&self) -> u32 {
+        leading_zero_bits(&self.target_hash)
+    }
+
+    /// Returns the number of candidates hashed per second during the search
+    pub fn hashrate(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// `Progress` is a report of a running search, passed to the callback of [`HashFinder::find_with_progress_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// The number of candidates hashed so far.
+    pub attempts: u64,
+    /// The wall-clock time since the search started.
+    pub elapsed: Duration,
+    /// The probability that a search finds an origin hash within this number of attempts, `1 - (1 - p)^attempts` for
+    /// the acceptance probability `p` of a candidate.
+    pub completion_probability: f64,
+}
+
+impl Progress {
+    /// Returns the number of candidates hashed per second so far
+    pub fn hashrate(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Finds an origin hash and periodically reports the attempts, the elapsed time and the completion probability
+    ///
+    /// This is [`HashFinder::find_with_progress`] with a [`Progress`] instead of the bare number of attempts, invoked
+    /// every [`PROGRESS_INTERVAL`](crate::PROGRESS_INTERVAL) attempts. Attempts are independent, so the probability
+    /// only tells how unusual the search has been so far: the expected remaining time is always
+    /// [`HashFinder::estimate_duration`]. Reports can be sent to another thread by calling `send` on a channel from the
+    /// callback.
+    ///
+    /// # Parameters
+    ///
+    /// - `on_progress`: The callback receiving the progress of the search.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::mpsc;
+    /// use pow_account::HashFinder;
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let origin_hash = HashFinder::new(4).find_with_progress_stats(|progress| {
+    ///     let _ = sender.send(progress);
+    /// });
+    ///
+    /// for progress in receiver.try_iter() {
+    ///     println!("{:.0} hashes/s, {:.0}% done", progress.hashrate(), 100.0 * progress.completion_probability);
+    /// }
+    /// assert!(HashFinder::new(4).check_bytes(&origin_hash));
+    /// ```
+    pub fn find_with_progress_stats<F: FnMut(Progress)>(&self, mut on_progress: F) -> [u8; 32] {
+        let started_at = Instant::now();
+        self.find_with_progress(|attempts| {
+            on_progress(Progress {
+                attempts,
+                elapsed: started_at.elapsed(),
+                completion_probability: self.completion_probability(attempts),
+            })
+        })
+    }
+
+    /// Returns the probability that a search finds an origin hash within `attempts` attempts
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(4);
+    /// let attempts = hash_finder.expected_attempts() as u64;
+    ///
+    /// // About 63% of the searches end within the expected number of attempts
+    /// assert!((hash_finder.completion_probability(attempts) - 0.632).abs() < 1e-3);
+    /// ```
+    pub fn completion_probability(&self, attempts: u64) -> f64 {
+        if attempts == 0 {
+            return 0.0;
+        }
+        -((-self.accept_probability()).ln_1p() * attempts as f64).exp_m1()
+    }
+
+    /// Finds an origin hash and reports the target hash, the number of attempts and the elapsed time
+    ///
+    /// The search is the same as [`HashFinder::find`]; the [`Solution`] only adds the metrics needed to log the
+    /// difficulty and the latency of the search.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(3);
+    /// let solution = hash_finder.find_with_stats();
+    ///
+    /// assert!(solution.attempts >= 1);
+    /// assert!(solution.achieved_bits() >= 12);
+    /// assert!(hash_finder.check(hex::encode(solution.origin_hash)).unwrap());
+    /// ```
+    pub fn find_with_stats(&self) -> Solution {
+        let started_at = Instant::now();
+        let (origin_hash, attempts) = self
+            .find_bounded(
+                &mut OsRng,
+                &mut SaltCounter::reserve(),
+                u64::MAX,
+                &mut |_| {},
+            )
+            .expect("no origin hash found in 2^64 attempts");
+
+        Solution {
+            origin_hash,
+            target_hash: self.target_hash(origin_hash),
+            attempts,
+            elapsed: started_at.elapsed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::PROGRESS_INTERVAL;
+
+    #[test]
+    fn solution_describes_the_found_origin_hash() {
+        let hash_finder = HashFinder::with_version(3, 2).with_rounds(3);
+        let solution = hash_finder.find_with_stats();
+
+        assert!(hash_finder.check_bytes(&solution.origin_hash));
+        assert_eq!(
+            solution.target_hash,
+            hash_finder.target_hash(solution.origin_hash)
+        );
+        assert!(solution.achieved_bits() >= hash_finder.difficulty_bits());
+        assert!(solution.attempts >= 1);
+        assert!(solution.hashrate() > 0.0)
+    }
+
+    #[test]
+    fn progress_is_reported_with_growing_probability() {
+        let hash_finder = HashFinder::with_bits(64);
+        let mut reports = Vec::new();
+
+        // The search never ends at this difficulty, so it is stopped by the panic of the third report
+        let search = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            hash_finder.find_with_progress_stats(|progress| {
+                reports.push(progress);
+                assert!(reports.len() < 3)
+            })
+        }));
+        assert!(search.is_err());
+
+        let attempts: Vec<u64> = reports.iter().map(|progress| progress.attempts).collect();
+        assert_eq!(
+            attempts,
+            [
+                PROGRESS_INTERVAL,
+                2 * PROGRESS_INTERVAL,
+                3 * PROGRESS_INTERVAL
+            ]
+        );
+        assert!(reports[0].completion_probability < reports[2].completion_probability);
+        assert!(reports[0].elapsed <= reports[2].elapsed);
+        assert!(reports[2].hashrate() > 0.0)
+    }
+
+    #[test]
+    fn completion_probability_is_bounded() {
+        assert_eq!(HashFinder::new(4).completion_probability(0), 0.0);
+        assert_eq!(HashFinder::with_bits(0).completion_probability(1), 1.0);
+        assert!(HashFinder::with_bits(250).completion_probability(u64::MAX) < 1e-50);
+        assert!((HashFinder::with_bits(1).completion_probability(2) - 0.75).abs() < 1e-12)
+    }
+}
@@ -0,0 +1,292 @@
+//! Replay protection, so that every proof is accepted at most once.
+//!
+//! A proof bound to an expiring [`Challenge`] can be submitted again and again until the challenge expires.
+//! [`HashFinder::check_and_consume`] records every accepted proof in a [`SolutionStore`] until its challenge expires
+//! and refuses the proofs it has already recorded. The lifetime of the challenge is bounded by the server, so a client
+//! can't make a record outlive the challenges the server issues. [`MemorySolutionStore`] keeps the records of a single process;
+//! services with several instances implement the trait on a shared database instead.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::challenge::unix_time;
+use crate::{decode_origin_hash, Binding, Challenge, HashFinder, PowError};
+
+/// `SolutionStore` records the proofs that were accepted until they expire.
+///
+/// A proof is identified by its 32-byte target hash, which covers the challenge, the origin hash and the settings of
+/// the HashFinder, so the same origin hash found for two challenges makes two distinct proofs.
+pub trait SolutionStore {
+    /// Records a proof until `expires_at`, in seconds since the Unix epoch, unless it is already recorded
+    ///
+    /// The check and the insertion must be atomic, so that a proof submitted to several threads or instances at once
+    /// is only recorded by one of them. A record may be forgotten once it expired.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `true` if the proof was recorded now, or `false` if it was already recorded.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::StoreUnavailable`] if the store can't be reached, in which case the proof
+    /// must not be accepted.
+    fn insert(&self, key: [u8; 32], expires_at: u64) -> Result<bool, PowError>;
+}
+
+/// `MemorySolutionStore` is a [`SolutionStore`] in the memory of the process.
+///
+/// Expired records are evicted whenever the number of records has doubled since the last eviction, so the memory
+/// used stays proportional to the number of proofs whose challenges are still valid.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use pow_account::{Challenge, HashFinder, MemorySolutionStore, PowError};
+///
+/// let store = MemorySolutionStore::new();
+/// let hash_finder = HashFinder::new(3);
+///
+/// let ttl = Duration::from_secs(60);
+/// let challenge = Challenge::issue(ttl);
+/// let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+///
+/// assert_eq!(hash_finder.check_and_consume(&store, &challenge, ttl, &origin_hash), Ok(true));
+/// assert_eq!(
+///     hash_finder.check_and_consume(&store, &challenge, ttl, &origin_hash),
+///     Err(PowError::AlreadyUsed)
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct MemorySolutionStore {
+    records: Mutex<Records>,
+}
+
+#[derive(Debug, Default)]
+struct Records {
+    expirations: HashMap<[u8; 32], u64>,
+    evict_at_len: usize,
+}
+
+/// Smallest number of records before expired records are evicted.
+const MIN_EVICTION_LEN: usize = 64;
+
+impl MemorySolutionStore {
+    /// Returns an empty store
+    pub fn new() -> Self {
+        MemorySolutionStore::default()
+    }
+
+    /// Returns the number of records, including expired records that weren't evicted yet
+    pub fn len(&self) -> usize {
+        self.records().expirations.len()
+    }
+
+    /// Returns `true` if the store holds no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evicts the records that expired
+    pub fn evict_expired(&self) {
+        self.records().evict(unix_time());
+    }
+
+    fn records(&self) -> std::sync::MutexGuard<'_, Records> {
+        self.records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn insert_at(&self, key: [u8; 32], expires_at: u64, now: u64) -> bool {
+        let mut records = self.records();
+        if records.expirations.len() >= records.evict_at_len.max(MIN_EVICTION_LEN) {
+            records.evict(now);
+        }
+
+        match records.expirations.get(&key) {
+            Some(&recorded_until) if recorded_until > now => false,
+            _ => {
+                records.expirations.insert(key, expires_at);
+                true
+            }
+        }
+    }
+}
+
+impl Records {
+    fn evict(&mut self, now: u64) {
+        self.expirations
+            .retain(|_, &mut expires_at| expires_at > now);
+        self.evict_at_len = 2 * self.expirations.len();
+    }
+}
+
+impl SolutionStore for MemorySolutionStore {
+    fn insert(&self, key: [u8; 32], expires_at: u64) -> Result<bool, PowError> {
+        Ok(self.insert_at(key, expires_at, unix_time()))
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Determines whether an origin hash was found for a challenge that is still valid, accepting it at most once
+    ///
+    /// This is [`HashFinder::check_with_expiring_challenge`] followed, for a proof that passes, by recording it in
+    /// `store` until the challenge expires. A proof that fails is not recorded, so it doesn't use up the challenge.
+    /// Challenges with a lifetime longer than `max_ttl` are rejected before anything is recorded, so a record never
+    /// outlives `max_ttl` after the time the challenge was issued, which is never in the future.
+    ///
+    /// # Parameters
+    ///
+    /// - `store`: The record of the proofs accepted so far.
+    /// - `challenge`: The challenge issued to the client, as sent back by the client.
+    /// - `max_ttl`: The longest lifetime accepted, the longest the server issues.
+    /// - `origin_hash`: A string containing the hexadecimal representation of the hash to check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PowError::AlreadyUsed`] if the proof was already accepted, the errors of
+    /// [`HashFinder::check_with_expiring_challenge`] and the errors of the store.
+    pub fn check_and_consume<St, S>(
+        &self,
+        store: &St,
+        challenge: &Challenge,
+        max_ttl: Duration,
+        origin_hash: S,
+    ) -> Result<bool, PowError>
+    where
+        St: SolutionStore + ?Sized,
+        S: AsRef<[u8]>,
+    {
+        let target_hash =
+            match self.record_key(challenge, max_ttl, decode_origin_hash(origin_hash)?)? {
+                Some(target_hash) => target_hash,
+                None => return Ok(false),
+            };
+
+        match store.insert(target_hash, challenge.expires_at())? {
+            true => Ok(true),
+            false => Err(PowError::AlreadyUsed),
+        }
+    }
+
+    /// Returns the key [`HashFinder::check_and_consume`] records a proof under, or `None` if the proof doesn't meet
+    /// the difficulty, without touching a store.
+    pub(crate) fn record_key(
+        &self,
+        challenge: &Challenge,
+        max_ttl: Duration,
+        origin_hash: [u8; 32],
+    ) -> Result<Option<[u8; 32]>, PowError> {
+        challenge.validate_ttl(max_ttl)?;
+        challenge.validate()?;
+
+        let target_hash =
+            self.bound_target_hash(Binding::Challenge, &challenge.bytes(), origin_hash);
+        match self.meets_target(&target_hash) {
+            true => Ok(Some(target_hash)),
+            false => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn proof_is_accepted_once() {
+        let store = MemorySolutionStore::new();
+        let hash_finder = HashFinder::new(2);
+        let challenge = Challenge::issue(TTL);
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+
+        assert_eq!(
+            hash_finder.check_and_consume(&store, &challenge, TTL, &origin_hash),
+            Ok(true)
+        );
+        assert_eq!(
+            hash_finder.check_and_consume(&store, &challenge, TTL, &origin_hash),
+            Err(PowError::AlreadyUsed)
+        );
+        assert_eq!(store.len(), 1);
+
+        // The same challenge is consumed separately with another origin hash
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+        assert_eq!(
+            hash_finder.check_and_consume(&store, &challenge, TTL, &origin_hash),
+            Ok(true)
+        )
+    }
+
+    #[test]
+    fn failed_proofs_are_not_recorded() {
+        let store = MemorySolutionStore::new();
+        let challenge = Challenge::issue(TTL);
+        let origin_hash = HashFinder::new(2).find_with_challenge(&challenge);
+
+        assert!(HashFinder::new(64)
+            .check_and_consume(&store, &challenge, TTL, hex::encode(origin_hash))
+            .is_ok_and(|accepted| !accepted));
+        assert!(HashFinder::new(2)
+            .check_and_consume(&store, &Challenge::random(), TTL, hex::encode(origin_hash))
+            .is_err());
+        assert!(store.is_empty())
+    }
+
+    #[test]
+    fn records_never_outlive_the_maximum_lifetime() {
+        let store = MemorySolutionStore::new();
+        let hash_finder = HashFinder::new(2);
+
+        // A challenge made up by the client that would be recorded for 136 years
+        let mut nonce = Challenge::random().bytes();
+        nonce[..8].copy_from_slice(&unix_time().to_be_bytes());
+        nonce[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+        let challenge = Challenge::from_bytes(nonce);
+        let origin_hash = hex::encode(hash_finder.find_with_challenge(&challenge));
+
+        assert_eq!(
+            hash_finder.check_and_consume(&store, &challenge, TTL, &origin_hash),
+            Err(PowError::LifetimeTooLong {
+                ttl: u64::from(u32::MAX),
+                max_ttl: 60
+            })
+        );
+        assert!(store.is_empty())
+    }
+
+    #[test]
+    fn expired_records_are_replaced_and_evicted() {
+        let store = MemorySolutionStore::new();
+
+        assert!(store.insert_at([1; 32], 100, 50));
+        assert!(!store.insert_at([1; 32], 100, 99));
+        assert!(store.insert_at([1; 32], 200, 100));
+
+        for index in 1..MIN_EVICTION_LEN as u8 {
+            assert!(store.insert_at([index + 1; 32], 150, 100));
+        }
+        assert_eq!(store.len(), MIN_EVICTION_LEN);
+
+        // Reaching the eviction length drops the records that expired at 150
+        assert!(store.insert_at([0xff; 32], 300, 150));
+        assert_eq!(store.len(), 2);
+        assert!(!store.insert_at([1; 32], 300, 150))
+    }
+
+    #[test]
+    fn store_can_be_used_as_a_trait_object() {
+        let store: Box<dyn SolutionStore> = Box::new(MemorySolutionStore::new());
+        let expires_at = unix_time() + 60;
+
+        assert_eq!(store.insert([9; 32], expires_at), Ok(true));
+        assert_eq!(store.insert([9; 32], expires_at), Ok(false))
+    }
+}
@@ -0,0 +1,232 @@
+//! Verification of proofs read one per line from a stream.
+
+use std::io::{self, BufRead, ErrorKind, Read};
+use std::{iter, str};
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::{HashFinder, PowError};
+
+/// Maximum number of bytes of a line buffered by [`HashFinder::check_reader`]: 64 hexadecimal characters and up to 64
+/// bytes of surrounding whitespace.
+const MAX_LINE_LEN: usize = 128;
+
+impl<D: Digest<OutputSize = U32>> HashFinder<D> {
+    /// Checks the origin hashes read from a reader, one hexadecimal hash per line
+    ///
+    /// Lines are read lazily, so arbitrarily long streams such as a socket are verified without being loaded into
+    /// memory. Surrounding whitespace is trimmed and blank lines are skipped. Every other line yields the result of
+    /// [`HashFinder::check`] for that line, in order. At most 128 bytes of a line are buffered: a longer line yields
+    /// [`PowError::InvalidLength`] and the rest of it is skipped without being stored.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: The source of newline-delimited hexadecimal origin hashes.
+    ///
+    /// # Errors
+    ///
+    /// An item is an error if its line is not a valid hexadecimal representation of 32 bytes, or [`PowError::Io`] if
+    /// the line is not valid UTF-8 or if reading failed. The iterator ends after a read error, since the reader may
+    /// fail the same way forever.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use pow_account::HashFinder;
+    ///
+    /// let hash_finder = HashFinder::new(2);
+    /// let input = format!("{}\n\n{}\n", hash_finder.find_hex(), hash_finder.find_hex());
+    ///
+    /// let results: Vec<_> = hash_finder.check_reader(Cursor::new(input)).collect();
+    /// assert_eq!(results, [Ok(true), Ok(true)]);
+    /// ```
+    pub fn check_reader<R: BufRead>(
+        &self,
+        reader: R,
+    ) -> impl Iterator<Item = Result<bool, PowError>> {
+        let hash_finder = *self;
+        let mut reader = Some(reader);
+        let mut line = Vec::with_capacity(MAX_LINE_LEN);
+
+        iter::from_fn(move || loop {
+            match read_line(reader.as_mut()?, &mut line) {
+                Ok(None) => reader = None,
+                Ok(Some(len)) if len > MAX_LINE_LEN => {
+                    return Some(Err(PowError::InvalidLength { len, expected: 64 }))
+                }
+                Ok(Some(_)) => match str::from_utf8(&line) {
+                    Ok(line) if line.trim().is_empty() => continue,
+                    Ok(line) => return Some(hash_finder.check(line.trim())),
+                    Err(_) => return Some(Err(PowError::Io(ErrorKind::InvalidData))),
+                },
+                Err(err) => {
+                    reader = None;
+                    return Some(Err(err.into()));
+                }
+            }
+        })
+    }
+}
+
+/// Reads the next line into `line`, without its newline, and returns its full length, or `None` at the end of the
+/// stream
+///
+/// Only the first [`MAX_LINE_LEN`] bytes are stored: the rest of a longer line is skipped and only counted.
+fn read_line<R: BufRead>(reader: &mut R, line: &mut Vec<u8>) -> io::Result<Option<usize>> {
+    line.clear();
+    let read = reader
+        .by_ref()
+        .take(MAX_LINE_LEN as u64)
+        .read_until(b'\n', line)?;
+    if read == 0 {
+        return Ok(None);
+    }
+
+    match line.last() {
+        Some(b'\n') => {
+            line.pop();
+            Ok(Some(line.len()))
+        }
+        _ => Ok(Some(line.len() + skip_line(reader)?)),
+    }
+}
+
+/// Consumes the reader up to and including the next newline and returns the number of bytes before it.
+fn skip_line<R: BufRead>(reader: &mut R) -> io::Result<usize> {
+    let mut skipped = 0;
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if available.is_empty() {
+            return Ok(skipped);
+        }
+
+        match available.iter().position(|&byte| byte == b'\n') {
+            Some(end) => {
+                reader.consume(end + 1);
+                return Ok(skipped + end);
+            }
+            None => {
+                let len = available.len();
+                reader.consume(len);
+                skipped += len;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{BufReader, Cursor};
+
+    use super::*;
+
+    #[test]
+    fn each_non_blank_line_yields_a_result() {
+        let input = concat!(
+            "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e\n",
+            "\n",
+            "  3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4\t\r\n",
+            "3c+727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4\n",
+            "   \n",
+            "51ad0600\n",
+            "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e",
+        );
+
+        let results: Vec<_> = HashFinder::new(4)
+            .check_reader(Cursor::new(input))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                Ok(true),
+                Ok(false),
+                Err(PowError::InvalidHex(
+                    hex::FromHexError::InvalidHexCharacter { c: '+', index: 2 }
+                )),
+                Err(PowError::InvalidLength {
+                    len: 8,
+                    expected: 64
+                }),
+                Ok(true),
+            ]
+        )
+    }
+
+    #[test]
+    fn unreadable_lines_are_reported_and_reading_continues() {
+        let input: &[u8] =
+            b"\xff\xfe\n51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e\n";
+
+        let results: Vec<_> = HashFinder::new(4).check_reader(input).collect();
+        assert_eq!(
+            results,
+            vec![Err(PowError::Io(ErrorKind::InvalidData)), Ok(true)]
+        )
+    }
+
+    #[test]
+    fn long_lines_are_rejected_without_being_buffered() {
+        let input = format!(
+            "{}\n51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e\n{}",
+            "a".repeat(100_000),
+            "b".repeat(MAX_LINE_LEN)
+        );
+
+        let results: Vec<_> = HashFinder::new(4)
+            .check_reader(Cursor::new(input))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                Err(PowError::InvalidLength {
+                    len: 100_000,
+                    expected: 64
+                }),
+                Ok(true),
+                Err(PowError::InvalidLength {
+                    len: MAX_LINE_LEN,
+                    expected: 64
+                }),
+            ]
+        )
+    }
+
+    #[test]
+    fn lines_up_to_the_limit_are_checked() {
+        let hash = "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e";
+        let input = format!("{hash}{}\n{hash}{}", " ".repeat(63), " ".repeat(64));
+
+        let results: Vec<_> = HashFinder::new(4)
+            .check_reader(Cursor::new(input))
+            .collect();
+        assert_eq!(results, vec![Ok(true), Ok(true)])
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    #[test]
+    fn reading_ends_after_an_io_error() {
+        let input =
+            Cursor::new("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e\n");
+        let reader = BufReader::new(input.chain(FailingReader));
+
+        let results: Vec<_> = HashFinder::new(4).check_reader(reader).collect();
+        assert_eq!(
+            results,
+            vec![Ok(true), Err(PowError::Io(ErrorKind::BrokenPipe))]
+        )
+    }
+}
@@ -0,0 +1,153 @@
+//! Searches for asynchronous applications, offloaded to the Tokio blocking thread pool.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use blake2::digest::consts::U32;
+use blake2::Digest;
+
+use crate::HashFinder;
+
+/// Sets the cancellation flag of a search when the future awaiting it is dropped.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl<D: Digest<OutputSize = U32> + 'static> HashFinder<D> {
+    /// Finds an origin hash without blocking the asynchronous runtime
+    ///
+    /// The search runs on the blocking thread pool of the current Tokio runtime with
+    /// [`tokio::task::spawn_blocking`], so other tasks keep making progress while it runs. Dropping the returned
+    /// future cancels the search, as with [`HashFinder::find_cancellable`], which makes it compose with
+    /// `tokio::time::timeout` the way [`HashFinder::find_with_timeout`] bounds a synchronous search. The future
+    /// doesn't borrow the HashFinder, so it can be spawned as a task of its own.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a 32-byte array containing the generated hash.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is not called from within a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::HashFinder;
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let hash_finder = HashFinder::new(3);
+    /// let origin_hash = hash_finder.find_async().await;
+    /// assert!(hash_finder.check_bytes(&origin_hash));
+    ///
+    /// let search = HashFinder::new(64).find_async();
+    /// assert!(tokio::time::timeout(Duration::from_millis(10), search).await.is_err());
+    /// # });
+    /// ```
+    pub fn find_async(&self) -> impl Future<Output = [u8; 32]> + Send + 'static {
+        let hash_finder = *self;
+
+        async move {
+            let cancel = CancelOnDrop(Arc::new(AtomicBool::new(false)));
+            let flag = Arc::clone(&cancel.0);
+
+            let search = tokio::task::spawn_blocking(move || hash_finder.find_cancellable(&flag));
+            match search.await {
+                Ok(origin_hash) => origin_hash.expect("search cancelled while awaited"),
+                Err(err) => std::panic::resume_unwind(err.into_panic()),
+            }
+        }
+    }
+
+    /// Finds an origin hash without blocking the asynchronous runtime, giving up once `cancel` completes
+    ///
+    /// The search runs as with [`HashFinder::find_async`] and is stopped as soon as the `cancel` future completes,
+    /// for instance when a client disconnects or a `CancellationToken` of `tokio-util` is cancelled.
+    ///
+    /// # Parameters
+    ///
+    /// - `cancel`: The future whose completion stops the search.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Some` origin hash, or `None` if `cancel` completed first.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is not called from within a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pow_account::HashFinder;
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let disconnected = tokio::time::sleep(Duration::from_millis(10));
+    /// let origin_hash = HashFinder::new(64).find_async_cancellable(disconnected).await;
+    ///
+    /// assert_eq!(origin_hash, None);
+    /// # });
+    /// ```
+    pub fn find_async_cancellable<F: Future + Send>(
+        &self,
+        cancel: F,
+    ) -> impl Future<Output = Option<[u8; 32]>> + Send {
+        let search = self.find_async();
+
+        async move {
+            tokio::select! {
+                origin_hash = search => Some(origin_hash),
+                _ = cancel => None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::future;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn awaited_search_passes_the_check() {
+        let hash_finder = HashFinder::new(3);
+        let origin_hash = hash_finder.find_async().await;
+
+        assert!(hash_finder.check(hex::encode(origin_hash)).unwrap())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn timed_out_search_is_cancelled() {
+        // The runtime waits for its blocking tasks on shutdown, so this test only ends if the search stopped
+        let search = HashFinder::new(64).find_async();
+        let result = tokio::time::timeout(Duration::from_millis(20), search).await;
+
+        assert!(result.is_err())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn search_stops_when_the_cancellation_future_completes() {
+        let cancel = tokio::time::sleep(Duration::from_millis(20));
+        assert_eq!(
+            HashFinder::new(64).find_async_cancellable(cancel).await,
+            None
+        );
+
+        let hash_finder = HashFinder::new(3);
+        let origin_hash = hash_finder
+            .find_async_cancellable(future::pending::<()>())
+            .await
+            .unwrap();
+        assert!(hash_finder.check_bytes(&origin_hash))
+    }
+}
@@ -0,0 +1,108 @@
+//! Known-answer vectors for checking that a build reproduces the hashing construction.
+//!
+//! Every vector is a pair of a hexadecimal origin hash and the exact number of leading zero bits of its target hash.
+//! Each origin hash therefore passes the check of `HashFinder::with_bits(bits)` and fails the check of
+//! `HashFinder::with_bits(bits + 1)` with the settings the list is documented for.
+//!
+//! # Example
+//! ```
+//! use pow_account::{test_vectors, HashFinder};
+//!
+//! for &(origin_hash, bits) in test_vectors::BLAKE2S {
+//!     assert!(HashFinder::with_bits(bits).check(origin_hash).unwrap());
+//!     assert!(!HashFinder::with_bits(bits + 1).check(origin_hash).unwrap());
+//! }
+//! ```
+
+/// Vectors for the default settings of [`HashFinder::with_bits`](crate::HashFinder::with_bits), with Blake2s.
+pub const BLAKE2S: &[(&str, u32)] = &[
+    (
+        "c37289b48949a7d172346cb3e5600da905f53e7c022d364836dcf57db4de33fa",
+        1,
+    ),
+    (
+        "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4",
+        12,
+    ),
+    (
+        "73b8f38be026335eb78946ea30434ff3cee4cff6544d49b4772f80397d40e72f",
+        17,
+    ),
+    (
+        "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e",
+        20,
+    ),
+];
+
+/// Vectors for the default settings with SHA-256 chosen by [`HashFinder::with_digest`](crate::HashFinder::with_digest).
+pub const SHA256: &[(&str, u32)] = &[
+    (
+        "271740448e5353d798b8ebf21559e05a5544604f770b3e67ec1fb1dee34f46c0",
+        18,
+    ),
+    (
+        "73b8f38be026335eb78946ea30434ff3cee4cff6544d49b4772f80397d40e72f",
+        3,
+    ),
+];
+
+/// Vectors for [`HashFinder::find_seeded`](crate::HashFinder::find_seeded) with the default settings of
+/// [`HashFinder::with_bits`](crate::HashFinder::with_bits): a hexadecimal seed, the number of leading zero bits and the
+/// origin hash the search returns.
+pub const SEEDED: &[(&str, u32, &str)] = &[
+    (
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        8,
+        "2970f5d17800b4664fe03d6e333c2f7b9d9c43ba5e9498e213863a606f954bf6",
+    ),
+    (
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        16,
+        "e0df3ce445242ceaea4fb33b031151a42ebb1f754a735d79e1abc4cea69047ca",
+    ),
+];
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use sha2::Sha256;
+
+    use super::*;
+    use crate::{decode_origin_hash, HashFinder};
+
+    #[test]
+    fn blake2s_vectors_verify_at_exactly_their_difficulty() {
+        for &(origin_hash, bits) in BLAKE2S {
+            assert!(HashFinder::with_bits(bits).check(origin_hash).unwrap());
+            assert!(!HashFinder::with_bits(bits + 1).check(origin_hash).unwrap());
+            assert_eq!(
+                HashFinder::difficulty_of(&decode_origin_hash(origin_hash).unwrap()),
+                bits
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_searches_reproduce_their_vectors() {
+        for &(seed, bits, origin_hash) in SEEDED {
+            let mut hash_finder = HashFinder::with_bits(bits);
+            let seed = decode_origin_hash(seed).unwrap();
+            assert_eq!(hex::encode(hash_finder.find_seeded(seed)), origin_hash);
+
+            // The counter salt only affects the searches from random entropy
+            hash_finder.set_counter_salt(false);
+            assert_eq!(hex::encode(hash_finder.find_seeded(seed)), origin_hash);
+        }
+    }
+
+    #[test]
+    fn sha256_vectors_verify_at_exactly_their_difficulty() {
+        for &(origin_hash, bits) in SHA256 {
+            let hash_finder = HashFinder::with_bits(bits).with_digest::<Sha256>();
+            assert!(hash_finder.check(origin_hash).unwrap());
+
+            let hash_finder = HashFinder::with_bits(bits + 1).with_digest::<Sha256>();
+            assert!(!hash_finder.check(origin_hash).unwrap());
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Self-describing proofs carrying their own difficulty.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{decode_origin_hash, HashFinder, PowError, MAX_DIFFICULTY_BITS};
+
+/// Scheme prepended to every token.
+const TOKEN_SCHEME: &str = "pow";
+
+/// `PowToken` bundles an origin hash with the difficulty it was found for.
+///
+/// A token is written as `pow:<bits>:<hex>`, where `bits` is the number of leading zero bits required of the target
+/// hash in decimal without leading zeros and `hex` is the hexadecimal representation of the origin hash. Verifiers
+/// don't need to know the difficulty in advance, but they should still reject tokens whose difficulty is lower than
+/// their own policy requires.
+///
+/// # Example
+/// ```
+/// use pow_account::{HashFinder, PowToken};
+///
+/// let token = HashFinder::with_bits(10).mint().to_string();
+/// assert!(token.starts_with("pow:10:"));
+///
+/// let token: PowToken = token.parse().unwrap();
+/// assert!(token.verify());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PowToken {
+    bits: u32,
+    origin_hash: [u8; 32],
+}
+
+impl PowToken {
+    /// Returns the number of leading zero bits the token claims
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns the origin hash of the token
+    pub fn origin_hash(&self) -> [u8; 32] {
+        self.origin_hash
+    }
+
+    /// Determines whether the origin hash meets the difficulty embedded in the token
+    ///
+    /// The target is recomputed from the embedded number of bits, as with [`verify`](crate::verify).
+    pub fn verify(&self) -> bool {
+        crate::verify(&self.origin_hash, self.bits)
+    }
+}
+
+impl fmt::Display for PowToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{TOKEN_SCHEME}:{}:{}",
+            self.bits,
+            hex::encode(self.origin_hash)
+        )
+    }
+}
+
+/// Parses a token written as `pow:<bits>:<hex>`.
+///
+/// # Errors
+///
+/// Parsing returns [`PowError::InvalidFormat`] if the token doesn't follow the format, [`PowError::TooManyBits`] if
+/// the number of bits exceeds [`MAX_DIFFICULTY_BITS`], and [`PowError::InvalidLength`] or [`PowError::InvalidHex`]
+/// if the origin hash is not 64 hexadecimal characters.
+impl FromStr for PowToken {
+    type Err = PowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(3, ':');
+
+        let (bits, origin_hash) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(TOKEN_SCHEME), Some(bits), Some(origin_hash)) => (bits, origin_hash),
+            _ => return Err(PowError::InvalidFormat),
+        };
+
+        let bits = Some(bits)
+            .filter(|bits| bits.bytes().all(|c| c.is_ascii_digit()))
+            .filter(|bits| bits.len() == 1 || !bits.starts_with('0'))
+            .and_then(|bits| bits.parse::<u32>().ok())
+            .ok_or(PowError::InvalidFormat)?;
+        if bits > MAX_DIFFICULTY_BITS {
+            return Err(PowError::TooManyBits { bits });
+        }
+
+        Ok(PowToken {
+            bits,
+            origin_hash: decode_origin_hash(origin_hash)?,
+        })
+    }
+}
+
+impl HashFinder {
+    /// Finds an origin hash and bundles it with its difficulty in a [`PowToken`]
+    ///
+    /// The embedded difficulty is the number of leading zero bits of the target. The search uses the default
+    /// hashing construction, which is the one [`PowToken::verify`] checks against, whatever version this
+    /// HashFinder was created with.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::HashFinder;
+    ///
+    /// let token = HashFinder::new(3).mint();
+    ///
+    /// assert_eq!(token.bits(), 12);
+    /// assert!(token.verify());
+    /// ```
+    pub fn mint(&self) -> PowToken {
+        let bits = self.difficulty_bits();
+        let mut hash_finder = HashFinder::with_bits(bits);
+        hash_finder.set_counter_salt(self.counter_salt);
+
+        PowToken {
+            bits,
+            origin_hash: hash_finder.find(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn minted_token_verifies() {
+        let token = HashFinder::with_bits(8).mint();
+        let parsed: PowToken = token.to_string().parse().unwrap();
+
+        assert_eq!(parsed, token);
+        assert!(parsed.verify())
+    }
+
+    #[test]
+    fn tampered_difficulty_fails_verification() {
+        // The target hash starts with 12 zero bits
+        let hash = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+
+        let token: PowToken = format!("pow:12:{hash}").parse().unwrap();
+        assert!(token.verify());
+
+        let token: PowToken = format!("pow:13:{hash}").parse().unwrap();
+        assert!(!token.verify())
+    }
+
+    #[test]
+    fn malformed_tokens_are_rejected() {
+        let hash = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+
+        let cases = [
+            (format!("work:12:{hash}"), PowError::InvalidFormat),
+            (String::from("pow"), PowError::InvalidFormat),
+            (String::from("pow:12"), PowError::InvalidFormat),
+            (format!("pow:twelve:{hash}"), PowError::InvalidFormat),
+            (format!("pow::{hash}"), PowError::InvalidFormat),
+            (format!("pow:+12:{hash}"), PowError::InvalidFormat),
+            (format!("pow:012:{hash}"), PowError::InvalidFormat),
+            (format!("pow:00:{hash}"), PowError::InvalidFormat),
+            (format!("pow: 12:{hash}"), PowError::InvalidFormat),
+            (
+                format!("pow:257:{hash}"),
+                PowError::TooManyBits { bits: 257 },
+            ),
+            (
+                format!("pow:12:{}", &hash[..62]),
+                PowError::InvalidLength {
+                    len: 62,
+                    expected: 64,
+                },
+            ),
+            (
+                format!("pow:12:{hash}:extra"),
+                PowError::InvalidLength {
+                    len: 70,
+                    expected: 64,
+                },
+            ),
+            (
+                format!("pow:12:{}", hash.replace('a', "+")),
+                PowError::InvalidHex(hex::FromHexError::InvalidHexCharacter { c: '+', index: 2 }),
+            ),
+        ];
+
+        for (token, err) in cases {
+            assert_eq!(token.parse::<PowToken>(), Err(err), "{token}")
+        }
+        assert_eq!(
+            format!("pow:0:{hash}").parse::<PowToken>().unwrap().bits(),
+            0
+        )
+    }
+}
@@ -0,0 +1,151 @@
+//! Verification-only counterpart of [`HashFinder`](crate::HashFinder).
+
+use blake2::Blake2s256;
+
+use crate::{ct_lt, leading_zero_digits, Entropy, HashPrefix};
+#[cfg(feature = "std")]
+use crate::{decode_origin_hash, PowError};
+
+/// `Verifier` checks origin hashes against a difficulty target but, unlike
+/// [`HashFinder`](crate::HashFinder), has no way to find them.
+///
+/// It never touches the random number generator, so a service that must only validate proofs
+/// can depend on this type alone, and minting a proof on the server side becomes a compile error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Verifier {
+    target: [u8; 32],
+}
+
+impl Default for Verifier {
+    fn default() -> Self {
+        Verifier {
+            target: HashPrefix::default().target(),
+        }
+    }
+}
+
+impl Verifier {
+    /// Returns a Verifier struct with a specified number of target leading zeros
+    /// # Example
+    /// ```
+    /// use pow_account::Verifier;
+    ///
+    /// let verifier = Verifier::new(4);
+    ///
+    /// ```
+    pub fn new(difficulty: u8) -> Self {
+        Verifier {
+            target: HashPrefix::new(difficulty).target(),
+        }
+    }
+
+    /// Returns a Verifier struct that accepts target hashes strictly lower than `target`
+    /// # Example
+    /// ```
+    /// use pow_account::Verifier;
+    ///
+    /// let verifier = Verifier::from_target([0xff; 32]);
+    ///
+    /// ```
+    pub fn from_target(target: [u8; 32]) -> Self {
+        Verifier { target }
+    }
+
+    /// Determines whether a given origin hash produces a target hash below the configured target.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::{HashFinder, Verifier};
+    ///
+    /// let origin_hash = HashFinder::new(3).find();
+    /// assert!(Verifier::new(3).verify(&origin_hash));
+    /// ```
+    pub fn verify(&self, origin_hash: &[u8; 32]) -> bool {
+        ct_lt(
+            &Entropy::from(*origin_hash).hash::<Blake2s256>(),
+            &self.target,
+        )
+    }
+
+    /// Same as [`Verifier::verify`] for a hexadecimal representation of the origin hash.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the provided hash string is not a valid
+    /// hexadecimal representation of 32 bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::Verifier;
+    ///
+    /// let origin_hash = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+    /// assert!(Verifier::new(3).verify_hex(origin_hash).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn verify_hex(&self, origin_hash: &str) -> Result<bool, PowError> {
+        let origin_hash_bytes = decode_origin_hash(origin_hash)?;
+
+        Ok(self.verify(&origin_hash_bytes))
+    }
+
+    /// Returns the number of leading zeros of the target hash produced by an origin hash.
+    ///
+    /// The value is expressed in the same unit as [`Verifier::new`], so an origin hash is accepted
+    /// by `Verifier::new(n)` whenever `achieved_difficulty` returns at least `n`.
+    ///
+    /// # Example
+    /// ```
+    /// use pow_account::{HashFinder, Verifier};
+    ///
+    /// let origin_hash = HashFinder::new(3).find();
+    /// assert!(Verifier::default().achieved_difficulty(&origin_hash) >= 3);
+    /// ```
+    pub fn achieved_difficulty(&self, origin_hash: &[u8; 32]) -> u8 {
+        leading_zero_digits(origin_hash) as u8
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::*;
+    use crate::HashFinder;
+
+    #[test]
+    fn verifier_agrees_with_hash_finder() {
+        let hashes = [
+            "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4",
+            "73b8f38be026335eb78946ea30434ff3cee4cff6544d49b4772f80397d40e72f",
+            "51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e",
+        ];
+
+        for difficulty in 1..=6 {
+            for hash in hashes {
+                let expected = HashFinder::new(difficulty).check(String::from(hash));
+                assert_eq!(Verifier::new(difficulty).verify_hex(hash), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn from_target_matches_new() {
+        let target = HashPrefix::new(4).target();
+        assert_eq!(Verifier::from_target(target), Verifier::new(4))
+    }
+
+    #[test]
+    fn achieved_difficulty_counts_leading_zero_digits() {
+        let origin_hash: [u8; 32] =
+            hex::decode("51ad0600f06b0d57300a37952cea658410488748400628c8a2e7d712892d806e")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let target_hash = hex::encode(Entropy::from(origin_hash).hash::<Blake2s256>());
+        let zeros = target_hash.len() - target_hash.trim_start_matches('0').len();
+
+        assert_eq!(
+            Verifier::default().achieved_difficulty(&origin_hash),
+            zeros as u8
+        )
+    }
+}
@@ -0,0 +1,127 @@
+//! JavaScript bindings for browsers, exported through `wasm-bindgen`.
+//!
+//! The functions of this module are exported to JavaScript under the names given in their documentation. A search
+//! on the browser main thread blocks rendering and input until it returns, so long searches should use
+//! [`find_async`], which yields to the event loop between slices of attempts, or drive a [`Search`] step by step.
+//!
+//! The crate is built as a library, so a module for JavaScript is produced by building it as a `cdylib` and running
+//! `wasm-bindgen` on the result:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/pow_account.wasm
+//! ```
+
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::HashFinder;
+
+/// Number of attempts [`find_async`] makes between two yields when none is given.
+pub const DEFAULT_SLICE_ATTEMPTS: u32 = 20_000;
+
+/// Finds an origin hash for a target hash with `leading_zeros` leading zeros and returns it in hexadecimal
+///
+/// This is exported as `find`. It blocks the calling thread until an origin hash is found, so it is best called from
+/// a Web Worker. On the main thread use [`find_async`] instead.
+#[wasm_bindgen(js_name = find)]
+pub fn find_hex(leading_zeros: u8) -> String {
+    HashFinder::new(leading_zeros).find_hex()
+}
+
+/// Determines whether a hexadecimal origin hash produces a target hash with `leading_zeros` leading zeros
+///
+/// This is exported as `check`.
+///
+/// # Errors
+///
+/// The call throws an `Error` with the message of the [`PowError`](crate::PowError) if `origin_hash` is not a
+/// 64-character hexadecimal string.
+#[wasm_bindgen(js_name = check)]
+pub fn check_hex(origin_hash: &str, leading_zeros: u8) -> Result<bool, JsError> {
+    HashFinder::new(leading_zeros)
+        .check(origin_hash)
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Finds an origin hash without freezing the page and resolves to it in hexadecimal
+///
+/// This is exported as `findAsync` and returns a `Promise`. The search runs on the calling thread in slices of
+/// `slice_attempts` attempts, [`DEFAULT_SLICE_ATTEMPTS`] if it is `0`, and after each slice it waits for a
+/// `setTimeout` callback, which lets the browser render and handle input before the search carries on.
+#[wasm_bindgen(js_name = findAsync)]
+pub async fn find_async(leading_zeros: u8, slice_attempts: u32) -> String {
+    let mut search = Search::new(leading_zeros);
+    loop {
+        if let Some(origin_hash) = search.step(slice_attempts) {
+            return origin_hash;
+        }
+        yield_to_event_loop().await;
+    }
+}
+
+/// `Search` is a resumable search that the caller advances one slice at a time.
+///
+/// It is exported as the class `Search`, for pages that schedule the work themselves, for instance with
+/// `requestIdleCallback`.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Search {
+    hash_finder: HashFinder,
+    attempts: u64,
+}
+
+#[wasm_bindgen]
+impl Search {
+    /// Returns a search for a target hash with `leading_zeros` leading zeros
+    #[wasm_bindgen(constructor)]
+    pub fn new(leading_zeros: u8) -> Search {
+        Search {
+            hash_finder: HashFinder::new(leading_zeros),
+            attempts: 0,
+        }
+    }
+
+    /// Makes up to `max_attempts` attempts, [`DEFAULT_SLICE_ATTEMPTS`] if it is `0`, and returns the hexadecimal
+    /// origin hash if one of them succeeded
+    pub fn step(&mut self, max_attempts: u32) -> Option<String> {
+        let max_attempts = match max_attempts {
+            0 => DEFAULT_SLICE_ATTEMPTS,
+            max_attempts => max_attempts,
+        };
+
+        match self.hash_finder.find_with_limit(u64::from(max_attempts)) {
+            Some(origin_hash) => Some(hex::encode(origin_hash)),
+            None => {
+                self.attempts += u64::from(max_attempts);
+                None
+            }
+        }
+    }
+
+    /// Returns the number of attempts made by the slices that didn't find an origin hash
+    ///
+    /// This is exported as a `Number`, which is exact up to 2<sup>53</sup> attempts.
+    #[wasm_bindgen(getter)]
+    pub fn attempts(&self) -> f64 {
+        self.attempts as f64
+    }
+}
+
+/// Resolves once the event loop has run a `setTimeout` callback, so that pending rendering and input are handled.
+async fn yield_to_event_loop() {
+    let promise = Promise::new(&mut |resolve, _reject| {
+        let global = js_sys::global();
+        let set_timeout = Reflect::get(&global, &JsValue::from_str("setTimeout"))
+            .expect("the global object has a setTimeout function")
+            .unchecked_into::<Function>();
+        set_timeout
+            .call2(&global, &resolve, &JsValue::from(0))
+            .expect("setTimeout accepts a callback");
+    });
+    JsFuture::from(promise)
+        .await
+        .expect("the timeout promise is never rejected");
+}
@@ -0,0 +1,70 @@
+//! Runs the `pow-account` binary, which is only built with the `cli` feature.
+
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+use pow_account::HashFinder;
+
+fn pow_account(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pow-account"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone())
+        .unwrap()
+        .trim()
+        .to_owned()
+}
+
+#[test]
+fn found_hash_passes_the_check_command() {
+    let output = pow_account(&["find", "--difficulty", "3"]);
+    assert!(output.status.success());
+
+    let origin_hash = stdout(&output);
+    assert!(HashFinder::new(3).check(&origin_hash).unwrap());
+
+    let output = pow_account(&["check", &origin_hash, "--difficulty", "3"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "true")
+}
+
+#[test]
+fn check_exit_status_distinguishes_insufficient_and_malformed_hashes() {
+    // The target hash starts with 3 leading zeros
+    let origin_hash = "3ca727c7fefed674268797882ff4b26c8e28873ee6fbfae71d9ccc35e24444d4";
+
+    let output = pow_account(&["check", origin_hash, "--difficulty", "4"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "false");
+
+    let output = pow_account(&["check", &origin_hash[..63], "--difficulty", "4"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("expected 64"))
+}
+
+#[test]
+fn json_output_round_trips_through_serde() {
+    let output = pow_account(&["find", "--difficulty", "2", "--json"]);
+    let solution: pow_account::Solution = serde_json::from_str(&stdout(&output)).unwrap();
+    assert!(HashFinder::new(2).check_bytes(&solution.origin_hash));
+
+    let origin_hash = hex::encode(solution.origin_hash);
+    let output = pow_account(&["--json", "check", &origin_hash, "-d", "2"]);
+    let report: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(report["valid"], true);
+    assert_eq!(report["difficulty"], 2)
+}
+
+#[test]
+fn bench_reports_a_positive_hashrate() {
+    let output = pow_account(&["bench", "--seconds", "0.1", "--json"]);
+    let report: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert!(report["hashrate"].as_f64().unwrap() > 0.0);
+
+    assert!(!pow_account(&["bench", "--seconds", "0"]).status.success())
+}
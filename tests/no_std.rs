@@ -0,0 +1,23 @@
+//! Finding and verifying with the API that remains available without the `std` feature.
+//!
+//! Run with `cargo test --no-default-features --test no_std` to check it against the `no_std` build of the library.
+
+use pow_account::{HashFinder, Verifier};
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[test]
+fn seeded_search_is_found_and_verified_without_std() {
+    let mut hash_finder = HashFinder::with_bits(8);
+    hash_finder.set_counter_salt(false);
+
+    let origin_hash = hash_finder.find_with_rng(&mut ChaCha20Rng::seed_from_u64(7));
+
+    assert_eq!(
+        origin_hash,
+        hash_finder.find_with_rng(&mut ChaCha20Rng::seed_from_u64(7))
+    );
+    assert!(hash_finder.check_bytes(&origin_hash));
+    assert!(HashFinder::difficulty_of(&origin_hash) >= 8);
+    assert!(Verifier::new(2).verify(&origin_hash))
+}
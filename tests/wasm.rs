@@ -0,0 +1,42 @@
+//! Bounded search and the JavaScript bindings in a browser-like environment, where threads and `Instant` are
+//! unavailable.
+//!
+//! Run with `cargo test --target wasm32-unknown-unknown --features wasm --test wasm`, which uses the
+//! `wasm-bindgen-test-runner` configured in `.cargo/config.toml`.
+
+#![cfg(target_arch = "wasm32")]
+
+use pow_account::HashFinder;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn bounded_search_runs_with_the_javascript_random_number_generator() {
+    let hash_finder = HashFinder::new(2);
+    let origin_hash = hash_finder.find_with_limit(1_000_000).unwrap();
+
+    assert!(hash_finder.check_bytes(&origin_hash));
+    assert!(hash_finder.check(hex::encode(origin_hash)).unwrap())
+}
+
+#[wasm_bindgen_test]
+fn exported_functions_find_and_check_hexadecimal_hashes() {
+    let origin_hash = pow_account::wasm::find_hex(2);
+
+    assert!(pow_account::wasm::check_hex(&origin_hash, 2).unwrap());
+    assert!(pow_account::wasm::check_hex("not a hash", 2).is_err())
+}
+
+#[wasm_bindgen_test]
+fn search_steps_count_the_failed_attempts() {
+    let mut search = pow_account::wasm::Search::new(30);
+
+    assert_eq!(search.step(100), None);
+    assert_eq!(search.step(100), None);
+    assert_eq!(search.attempts(), 200.0)
+}
+
+#[wasm_bindgen_test]
+async fn async_search_yields_between_slices() {
+    let origin_hash = pow_account::wasm::find_async(3, 100).await;
+    assert!(pow_account::wasm::check_hex(&origin_hash, 3).unwrap())
+}